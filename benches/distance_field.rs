@@ -0,0 +1,148 @@
+//! Compares the memory footprint and the speed of the distance field storage
+//! (parallel arrays) with an array of `Cell` records, as it was used before.
+//!
+//! Run with `cargo bench --bench distance_field`.
+
+use std::mem::size_of;
+use std::time::{Duration, Instant};
+
+use rs_sdf::data::{Cell, CellLayer, CellPosition};
+use rs_sdf::data::input::InputField;
+use rs_sdf::data::scalar::ScalarField;
+use rs_sdf::input::DistanceInput;
+use rs_sdf::input::image::PngInput;
+use rs_sdf::processor::SourceProcessor;
+use rs_sdf::processor::sweep::EightSideSweepProcessor;
+
+const RUNS: u32 = 5;
+
+fn main() {
+	for path in ["examples/assets/example_1_rgba_512x512.png", "examples/assets/example_10_rgba_3100x900.png"].iter() {
+		let source = PngInput::new(path).source_field().unwrap();
+		println!("{} ({}x{})", path, source.width, source.height);
+
+		let df = EightSideSweepProcessor {}.process(&source);
+		let cells = df.len();
+		println!("  memory   parallel arrays: {:>10} bytes ({:.2} bytes per cell)",
+				 df.storage_size(), df.storage_size() as f64 / cells as f64);
+		println!("  memory   cell records:    {:>10} bytes ({:.2} bytes per cell)",
+				 cells * size_of::<Cell>(), size_of::<Cell>() as f64);
+
+		// both sweeps have to find the same nearest cells
+		let records = sweep_cell_records(&source);
+		assert!(records.iter().zip(df.cells()).all(|(record, cell)| {
+			record.nearest_cell_position.map(|nearest| nearest.index) == cell.nearest_cell_position.map(|nearest| nearest.index)
+		}));
+
+		report("sweep    parallel arrays", || { EightSideSweepProcessor {}.process(&source); });
+		report("sweep    cell records", || { sweep_cell_records(&source); });
+		report("decode   parallel arrays", || { let _ = ScalarField::from(&df); });
+		report("collect  cell records", || { df.cells().count(); });
+	}
+}
+
+fn report(name: &str, mut run: impl FnMut()) {
+	let mut best = Duration::MAX;
+	for _ in 0..RUNS {
+		let start = Instant::now();
+		run();
+		best = best.min(start.elapsed());
+	}
+	println!("  {:<25} {:>8.2} ms", format!("{}:", name), best.as_secs_f64() * 1000.0);
+}
+
+/// The reference: the sweep of the `EightSideSweepProcessor` before the parallel arrays were introduced,
+/// i.e. the same visiting order and the same comparison of the cells on a vector of `Cell` records.
+fn sweep_cell_records(source: &InputField) -> Vec<Cell> {
+	let w = source.width as usize;
+	let mut cells: Vec<Cell> = source.data.iter()
+		.enumerate()
+		.map(|(index, value)| {
+			let layer = if *value { CellLayer::Foreground } else { CellLayer::Background };
+			Cell::new(layer, index as u64, (index % w) as u32, (index / w) as u32)
+		})
+		.collect();
+	sweep_down(&mut cells, w);
+	sweep_up(&mut cells, w);
+	cells
+}
+
+fn sweep_down(cells: &mut [Cell], w: usize) {
+	let h = cells.len() / w;
+	for index in 1..w {
+		compare_cells(cells, index, index - 1);
+	}
+	for index in (0..w - 1).rev() {
+		compare_cells(cells, index, index + 1);
+	}
+	let mut idx = w;
+	for _ in 1..h {
+		compare_cells(cells, idx, idx - w);
+		compare_cells(cells, idx, idx - w + 1);
+		idx += 1;
+		for _ in 1..w - 1 {
+			compare_cells(cells, idx, idx - 1);
+			compare_cells(cells, idx, idx - w);
+			compare_cells(cells, idx, idx - w - 1);
+			compare_cells(cells, idx, idx - w + 1);
+			idx += 1;
+		}
+		compare_cells(cells, idx, idx - 1);
+		compare_cells(cells, idx, idx - w);
+		compare_cells(cells, idx, idx - w - 1);
+		for _ in 0..w - 1 {
+			idx -= 1;
+			compare_cells(cells, idx, idx + 1);
+		}
+		idx += w;
+	}
+}
+
+fn sweep_up(cells: &mut [Cell], w: usize) {
+	let h = cells.len() / w;
+	let mut idx = w * h - 1;
+	for _ in 1..w {
+		idx -= 1;
+		compare_cells(cells, idx, idx + 1);
+	}
+	for _ in 1..w {
+		idx += 1;
+		compare_cells(cells, idx, idx - 1);
+	}
+	for _ in 1..h {
+		idx -= w;
+		compare_cells(cells, idx, idx + w);
+		compare_cells(cells, idx, idx + w - 1);
+		for _ in 1..w - 1 {
+			idx -= 1;
+			compare_cells(cells, idx, idx + 1);
+			compare_cells(cells, idx, idx + w);
+			compare_cells(cells, idx, idx + w + 1);
+			compare_cells(cells, idx, idx + w - 1);
+		}
+		idx -= 1;
+		compare_cells(cells, idx, idx + 1);
+		compare_cells(cells, idx, idx + w);
+		compare_cells(cells, idx, idx + w + 1);
+		for _ in 1..w {
+			idx += 1;
+			compare_cells(cells, idx, idx - 1);
+		}
+	}
+}
+
+fn compare_cells(cells: &mut [Cell], target_index: usize, source_index: usize) {
+	let source = &cells[source_index];
+	let candidate = if source.layer != cells[target_index].layer {
+		Some(CellPosition { x: source.x, y: source.y, index: source.index })
+	} else {
+		source.nearest_cell_position
+	};
+	if let Some(candidate) = candidate {
+		let target = &mut cells[target_index];
+		let distance = Cell::get_distance_squared(&target.x, &target.y, &candidate.x, &candidate.y);
+		if target.distance_to_nearest_squared().is_none_or(|existing| distance < existing) {
+			target.set_nearest_cell_position(candidate);
+		}
+	}
+}
@@ -1,10 +1,20 @@
-use crate::data::input::InputField;
+use bitvec::vec::BitVec;
+
+use crate::data::input::{InputField, Spacing, check_dimensions_and_buffer_size};
 
 pub mod input;
 pub mod builder;
 pub mod transformation;
 pub mod output;
 pub mod serialize;
+pub mod scalar;
+pub mod sampling;
+pub mod label;
+pub mod narrow_band;
+pub mod tile;
+pub mod update;
+pub mod geodesic;
+pub mod chamfer;
 
 #[derive(Debug, Clone)]
 /// A single cell of a distance field
@@ -13,27 +23,36 @@ pub struct Cell {
 	pub layer: CellLayer,
 
 	/// The index of the cell in the field when it is seen as a one dimensional list.
-	pub index: u32,
+	pub index: u64,
 
 	/// The horizontal position of the cell in the field.
-	pub x: u16,
+	pub x: u32,
 
 	/// The vertical position of the cell in the field.
-	pub y: u16,
+	pub y: u32,
 
 	/// The position of the nearest cell from the other layer.
 	pub nearest_cell_position: Option<CellPosition>,
+
+	/// The offset (in cells) of a nearest cell beyond a border of the field to its position
+	/// in the field (see `BoundaryProcessor`). This is (0, 0) for all other nearest cells.
+	pub border_offset: (i32, i32),
 }
 
+/// The index of a nearest cell, that is not a cell of the field: a virtual cell beyond a constant
+/// border (see `BoundaryProcessor`). The coordinates of such a nearest cell are the coordinates
+/// of the adjacent border cell, its actual position is given by the border offset.
+pub const NO_CELL_INDEX: u64 = u64::MAX;
+
 #[derive(Debug, Clone, Copy)]
 pub struct CellPosition {
-	pub x: u16,
-	pub y: u16,
-	pub index: u32,
+	pub x: u32,
+	pub y: u32,
+	pub index: u64,
 }
 
 impl CellPosition {
-	pub fn get_coordinates(&self) -> (u16, u16) {
+	pub fn get_coordinates(&self) -> (u32, u32) {
 		(self.x, self.y)
 	}
 }
@@ -51,24 +70,33 @@ pub enum CellLayer {
 }
 
 impl Cell {
-	pub fn new(layer: CellLayer, index: u32, x: u16, y: u16) -> Self {
+	pub fn new(layer: CellLayer, index: u64, x: u32, y: u32) -> Self {
 		Self {
 			index,
 			x,
 			y,
 			layer,
 			nearest_cell_position: None,
+			border_offset: (0, 0),
 		}
 	}
 
 	/// The absolute squared distance to the nearest cell with the opposite layer type.
 	/// This is `None`, if no nearest cell was detected (yet).
 	pub fn distance_to_nearest_squared(&self) -> Option<u64> {
-		if let Some(nearest) = &self.nearest_cell_position {
-			Some(Self::get_distance_squared(&self.x, &self.y, &nearest.x, &nearest.y))
-		} else {
-			None
-		}
+		self.nearest_vector().map(|(dx, dy)| {
+			let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+			(dx * dx).saturating_add(dy * dy)
+		})
+	}
+
+	/// The vector (in cells) from this cell to its nearest cell, including the border offset.
+	/// This is `None`, if no nearest cell was detected (yet).
+	pub fn nearest_vector(&self) -> Option<(i64, i64)> {
+		self.nearest_cell_position.map(|nearest| (
+			nearest.x as i64 - self.x as i64 + self.border_offset.0 as i64,
+			nearest.y as i64 - self.y as i64 + self.border_offset.1 as i64,
+		))
 	}
 
 	pub fn get_nearest_cell_position(&self) -> &Option<CellPosition> {
@@ -80,95 +108,328 @@ impl Cell {
 		self.nearest_cell_position = Some(pos);
 	}
 
-	pub fn get_distance_squared(first_x: &u16, first_y: &u16, second_x: &u16, second_y: &u16) -> u64 {
-		// TODO: we should check all the casts here
-		// TODO: maybe use appropriate rust functions here
-		let horiz_dist = i32::from(*first_x) - i32::from(*second_x);
-		let vert_dist = i32::from(*first_y) - i32::from(*second_y);
-		horiz_dist.pow(2) as u64 + vert_dist.pow(2) as u64
+	pub fn get_distance_squared(first_x: &u32, first_y: &u32, second_x: &u32, second_y: &u32) -> u64 {
+		// the squares can't overflow, but their sum can (for coordinates near u32::MAX)
+		let horiz_dist = first_x.abs_diff(*second_x) as u64;
+		let vert_dist = first_y.abs_diff(*second_y) as u64;
+		(horiz_dist * horiz_dist).saturating_add(vert_dist * vert_dist)
+	}
+}
+
+/// The packed coordinates of a cell without a nearest cell.
+/// No cell can have both coordinates at `u32::MAX`, since the width and height of a field are at most `u32::MAX`.
+pub(crate) const NO_NEAREST_CELL: u64 = u64::MAX;
+
+/// Pack the coordinates of a cell into a single value (x in the lower, y in the upper 32 bits).
+pub(crate) fn pack_coordinates(x: u32, y: u32) -> u64 {
+	x as u64 | (y as u64) << 32
+}
+
+pub(crate) fn unpack_coordinates(packed: u64) -> (u32, u32) {
+	(packed as u32, (packed >> 32) as u32)
+}
+
+/// The index of a nearest cell, see `NearestCells`.
+pub(crate) trait NearestIndex: Copy + PartialEq {
+	/// The value of cells without a nearest cell.
+	const NONE: Self;
+
+	fn from_index(index: usize) -> Self;
+
+	fn index(self) -> usize;
+}
+
+impl NearestIndex for u32 {
+	const NONE: Self = u32::MAX;
+
+	fn from_index(index: usize) -> Self {
+		index as u32
+	}
+
+	fn index(self) -> usize {
+		self as usize
+	}
+}
+
+impl NearestIndex for u64 {
+	const NONE: Self = u64::MAX;
+
+	fn from_index(index: usize) -> Self {
+		index as u64
+	}
+
+	fn index(self) -> usize {
+		self as usize
+	}
+}
+
+/// The index of the nearest cell of every cell of a distance field.
+/// Fields with less than 2^32 - 1 cells store the indices with 32 bit, larger fields with 64 bit.
+#[derive(Debug, Clone)]
+pub(crate) enum NearestCells {
+	Compact(Vec<u32>),
+	Wide(Vec<u64>),
+}
+
+impl NearestCells {
+	/// The storage for the given number of cells without nearest cells.
+	pub fn new(len: usize) -> Self {
+		if len < u32::MAX as usize {
+			NearestCells::Compact(vec![u32::NONE; len])
+		} else {
+			NearestCells::Wide(vec![u64::NONE; len])
+		}
+	}
+
+	pub fn get(&self, index: usize) -> Option<usize> {
+		match self {
+			NearestCells::Compact(nearest) => nearest_index(nearest[index]),
+			NearestCells::Wide(nearest) => nearest_index(nearest[index]),
+		}
+	}
+
+	pub fn set(&mut self, index: usize, nearest: Option<usize>) {
+		match self {
+			NearestCells::Compact(cells) => cells[index] = nearest.map_or(u32::NONE, u32::from_index),
+			NearestCells::Wide(cells) => cells[index] = nearest.map_or(u64::NONE, u64::from_index),
+		}
+	}
+
+	/// The number of bytes used to store the indices.
+	pub fn storage_size(&self) -> usize {
+		match self {
+			NearestCells::Compact(nearest) => nearest.len() * std::mem::size_of::<u32>(),
+			NearestCells::Wide(nearest) => nearest.len() * std::mem::size_of::<u64>(),
+		}
+	}
+}
+
+fn nearest_index<T: NearestIndex>(nearest: T) -> Option<usize> {
+	if nearest == T::NONE {
+		None
+	} else {
+		Some(nearest.index())
+	}
+}
+
+/// Calculates the coordinates of the cells of a field from their indices.
+/// Indices with up to 32 bit are divided by the width with a multiplication, which is
+/// considerably faster than a division (see Lemire et al., "Faster Remainder by Direct Computation").
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FieldCoordinates {
+	width: u32,
+	multiplier: u64,
+}
+
+impl FieldCoordinates {
+	pub fn new(width: u32) -> Self {
+		FieldCoordinates {
+			width,
+			// this is zero for a width of one (which takes the slow path)
+			multiplier: (u64::MAX / width as u64).wrapping_add(1),
+		}
+	}
+
+	#[inline]
+	pub fn of(&self, index: usize) -> (u32, u32) {
+		if index <= u32::MAX as usize && self.multiplier != 0 {
+			let y = ((self.multiplier as u128 * index as u128) >> 64) as u32;
+			(index as u32 - y * self.width, y)
+		} else {
+			((index % self.width as usize) as u32, (index / self.width as usize) as u32)
+		}
 	}
 }
 
 /// A two-dimensional distance field with cells.
-/// The maximum for the width and the height of the field is 2^16 (65.536) units,
-/// so the maximum size of the whole field is 2^32 (4.294.967.296) units.
+/// The width and the height of the field are limited to 2^32 - 1 units and the cells are indexed
+/// with 64 bit, so e.g. a field of 70.000 x 70.000 cells is supported (see `MAX_CELLS`).
+///
+/// The cells are stored as parallel arrays: one bit for the layer and the index of the nearest
+/// cell (four bytes, or eight bytes for fields with 2^32 - 1 cells or more) per cell. Everything else
+/// (the position and index of a cell and the position of its nearest cell) is derived from the indices.
+/// The `Cell` records of the field are created on demand, see `cell`, `get` and `cells`.
+///
+/// The spacing is taken from the input field, the nearest cells are the nearest cells in physical units.
+///
+/// Nearest cells beyond the borders of the field (see `BoundaryProcessor`) are stored at their
+/// position in the field (wrapped around or clamped to the border) together with their border offset.
+#[derive(Debug, Clone)]
 pub struct DistanceField {
-	pub data: Vec<Cell>,
-	pub width: u16,
-	pub height: u16,
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
+	pub(crate) layers: BitVec,
+	pub(crate) nearest: NearestCells,
+	pub(crate) border: Option<Border>,
+}
+
+/// The nearest cells beyond the borders of a field, see `DistanceField`.
+#[derive(Debug, Clone)]
+pub(crate) struct Border {
+	pub offsets: Vec<(i32, i32)>,
+
+	/// True for the cells, whose nearest cell is a virtual cell beyond a constant border.
+	pub virtual_cells: BitVec,
+}
+
+impl Border {
+	pub fn new(len: usize) -> Self {
+		Border {
+			offsets: vec![(0, 0); len],
+			virtual_cells: BitVec::repeat(false, len),
+		}
+	}
 }
 
 impl DistanceField {
 	// TODO: it is rather stupid to make a filtered distance field. The filter should be moved to the export stage.
 	pub fn filter_inner(source: &Self) -> Self {
-		let cells = source.data.iter().map(|cell| {
-			match cell.layer {
-				CellLayer::Foreground => cell.clone(),
-				CellLayer::Background => Cell {
-					index: cell.index,
-					x: cell.x,
-					y: cell.y,
-					layer: CellLayer::Background,
-					nearest_cell_position: None,
-				}
-			}
-		}).collect();
-		DistanceField {
-			width: source.width,
-			height: source.height,
-			data: cells,
-		}
+		Self::filter(source, false)
 	}
 
 	// TODO: it is rather stupid to make a filtered distance field. The filter should be moved to the export stage.
 	pub fn filter_outer(source: &Self) -> Self {
-		let cells = source.data.iter().map(|cell| {
-			match cell.layer {
-				CellLayer::Background => cell.clone(),
-				CellLayer::Foreground => Cell {
-					index: cell.index,
-					x: cell.x,
-					y: cell.y,
-					layer: CellLayer::Foreground,
-					nearest_cell_position: None,
-				}
+		Self::filter(source, true)
+	}
+
+	/// Remove the nearest cells of all cells on the given layer (true for foreground).
+	fn filter(source: &Self, removed_layer: bool) -> Self {
+		let mut field = source.clone();
+		for (index, is_foreground) in source.layers.iter().enumerate() {
+			if *is_foreground == removed_layer {
+				field.nearest.set(index, None);
 			}
-		}).collect();
-		DistanceField {
-			width: source.width,
-			height: source.height,
-			data: cells,
 		}
+		field
 	}
 
 	/// Initialize a DistanceField based on the given SourceField.
 	pub fn new(source: &InputField) -> Self {
-		let cells = source.data
-			.chunks(source.width as usize)
-			.enumerate()
-			.map(
-				|(y, row_values)| {
-					row_values
-						.iter()
-						.enumerate()
-						.map(
-							move |(x, &value)| {
-								Cell {
-									index: (x + y * source.height as usize) as u32,
-									x: x as u16,
-									y: y as u16,
-									nearest_cell_position: None,
-									layer: if value { CellLayer::Foreground } else { CellLayer::Background },
-								}
-							})
-				})
-			.flatten()
-			.collect();
+		DistanceField {
+			spacing: source.spacing,
+			..Self::from_layers(source.data.clone(), source.width, source.height)
+		}
+	}
 
+	/// Initialize a DistanceField from the given cells (in row-major order).
+	/// Only the layer, the coordinates and the border offset of the nearest cell are taken from the cells
+	/// (and whether the nearest cell is a virtual cell, see `NO_CELL_INDEX`),
+	/// the positions and indices are given by the order of the cells.
+	pub fn from_cells(cells: &[Cell], width: u32, height: u32) -> Self {
+		check_dimensions_and_buffer_size(width, height, cells.len());
+		let mut field = Self::from_layers(cells.iter().map(|cell| cell.layer == CellLayer::Foreground).collect(), width, height);
+		for (index, cell) in cells.iter().enumerate() {
+			if let Some(nearest) = cell.nearest_cell_position {
+				field.set_nearest_cell(index, nearest.x, nearest.y);
+				field.set_border_offset(index, cell.border_offset, nearest.index == NO_CELL_INDEX);
+			}
+		}
+		field
+	}
+
+	/// Initialize a DistanceField without nearest cells from the layers of its cells (true for foreground).
+	pub(crate) fn from_layers(layers: BitVec, width: u32, height: u32) -> Self {
+		check_dimensions_and_buffer_size(width, height, layers.len());
 		DistanceField {
-			data: cells,
-			width: source.width,
-			height: source.height,
+			width,
+			height,
+			spacing: Spacing::default(),
+			nearest: NearestCells::new(layers.len()),
+			layers,
+			border: None,
 		}
 	}
+
+	/// The number of cells.
+	pub fn len(&self) -> usize {
+		self.layers.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.layers.is_empty()
+	}
+
+	/// The number of bytes used to store the cells.
+	pub fn storage_size(&self) -> usize {
+		self.len().div_ceil(8)
+			+ self.nearest.storage_size()
+			+ self.border.as_ref().map_or(0, |border| border.offsets.len() * std::mem::size_of::<(i32, i32)>() + self.len().div_ceil(8))
+	}
+
+	pub fn layer(&self, index: usize) -> CellLayer {
+		if self.layers[index] {
+			CellLayer::Foreground
+		} else {
+			CellLayer::Background
+		}
+	}
+
+	/// The position of the nearest cell from the other layer of the cell with the given index.
+	/// The index of a virtual nearest cell beyond a constant border is `NO_CELL_INDEX`.
+	pub fn nearest_cell_position(&self, index: usize) -> Option<CellPosition> {
+		self.nearest.get(index).map(|nearest| {
+			let w = self.width as usize;
+			let is_virtual = self.border.as_ref().is_some_and(|border| border.virtual_cells[index]);
+			CellPosition {
+				x: (nearest % w) as u32,
+				y: (nearest / w) as u32,
+				index: if is_virtual { NO_CELL_INDEX } else { nearest as u64 },
+			}
+		})
+	}
+
+	/// Set the coordinates of the nearest cell from the other layer of the cell with the given index.
+	/// The nearest cell is a cell of the field, i.e. its border offset is (0, 0).
+	pub fn set_nearest_cell(&mut self, index: usize, x: u32, y: u32) {
+		self.nearest.set(index, Some(x as usize + y as usize * self.width as usize));
+		self.set_border_offset(index, (0, 0), false);
+	}
+
+	/// Set the offset of the nearest cell of the cell with the given index to its position in the field
+	/// and whether it is a virtual cell beyond a constant border.
+	/// The offsets are only stored, once a nearest cell beyond a border is set.
+	pub(crate) fn set_border_offset(&mut self, index: usize, offset: (i32, i32), is_virtual: bool) {
+		if self.border.is_none() && offset == (0, 0) && !is_virtual {
+			return;
+		}
+		let len = self.len();
+		let border = self.border.get_or_insert_with(|| Border::new(len));
+		border.offsets[index] = offset;
+		border.virtual_cells.set(index, is_virtual);
+	}
+
+	pub fn clear_nearest_cell(&mut self, index: usize) {
+		self.nearest.set(index, None);
+		self.set_border_offset(index, (0, 0), false);
+	}
+
+	/// The offset of the nearest cell of the cell with the given index to its position in the field,
+	/// see `Cell::border_offset`.
+	pub fn border_offset(&self, index: usize) -> (i32, i32) {
+		self.border.as_ref().map_or((0, 0), |border| border.offsets[index])
+	}
+
+	/// The cell with the given index.
+	pub fn cell(&self, index: usize) -> Cell {
+		let w = self.width as usize;
+		Cell {
+			layer: self.layer(index),
+			index: index as u64,
+			x: (index % w) as u32,
+			y: (index / w) as u32,
+			nearest_cell_position: self.nearest_cell_position(index),
+			border_offset: self.border_offset(index),
+		}
+	}
+
+	/// The cell at the given position.
+	pub fn get(&self, x: u32, y: u32) -> Cell {
+		self.cell(x as usize + y as usize * self.width as usize)
+	}
+
+	/// All cells in row-major order.
+	pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+		(0..self.len()).map(move |index| self.cell(index))
+	}
 }
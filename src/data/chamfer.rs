@@ -0,0 +1,127 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{CellLayer, CellPosition, DistanceField, NO_NEAREST_CELL, unpack_coordinates};
+use crate::data::scalar::{ScalarField, signed_distance};
+use crate::data::input::Spacing;
+use crate::data::serialize::ByteSerializer;
+use crate::processor::chamfer::ChamferMask;
+use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDepth};
+
+/// The integer chamfer distance of every cell to the nearest cell of the other layer,
+/// together with this nearest cell (the cell the distance was propagated from).
+/// The distances are weighted with the mask, e.g. a horizontal step costs 3 with the 3-4 mask.
+pub struct ChamferField {
+	pub width: u32,
+	pub height: u32,
+	pub mask: ChamferMask,
+	layers: BitVec,
+	distances: Vec<u32>,
+	nearest: Vec<u64>,
+}
+
+/// The distance of a cell without a nearest cell.
+pub(crate) const NO_DISTANCE: u32 = u32::MAX;
+
+impl ChamferField {
+	/// Create the field from the distances (`NO_DISTANCE` for cells without a nearest cell)
+	/// and the packed coordinates of the nearest cells of all cells.
+	pub(crate) fn new(width: u32, height: u32, mask: ChamferMask, layers: BitVec, distances: Vec<u32>, nearest: Vec<u64>) -> Self {
+		ChamferField {
+			width,
+			height,
+			mask,
+			layers,
+			distances,
+			nearest,
+		}
+	}
+
+	pub fn layer(&self, x: u32, y: u32) -> CellLayer {
+		if self.layers[self.index(x, y)] {
+			CellLayer::Foreground
+		} else {
+			CellLayer::Background
+		}
+	}
+
+	/// The weighted (integer) distance to the nearest cell of the other layer.
+	/// This is `None`, if the field has no cells of the other layer.
+	pub fn distance(&self, x: u32, y: u32) -> Option<u32> {
+		match self.distances[self.index(x, y)] {
+			NO_DISTANCE => None,
+			distance => Some(distance),
+		}
+	}
+
+	/// The nearest cell of the other layer.
+	pub fn nearest_cell_position(&self, x: u32, y: u32) -> Option<CellPosition> {
+		match self.nearest[self.index(x, y)] {
+			NO_NEAREST_CELL => None,
+			packed => {
+				let (x, y) = unpack_coordinates(packed);
+				Some(CellPosition {
+					x,
+					y,
+					index: x as u64 + y as u64 * self.width as u64,
+				})
+			}
+		}
+	}
+
+	fn index(&self, x: u32, y: u32) -> usize {
+		x as usize + y as usize * self.width as usize
+	}
+}
+
+/// Implementation of the From trait.
+/// The nearest cells of the resulting field are the nearest cells under the chamfer metric.
+impl From<&ChamferField> for DistanceField {
+	fn from(field: &ChamferField) -> Self {
+		let mut df = DistanceField::from_layers(field.layers.clone(), field.width, field.height);
+		for (index, nearest) in field.nearest.iter().enumerate() {
+			if *nearest != NO_NEAREST_CELL {
+				let (x, y) = unpack_coordinates(*nearest);
+				df.set_nearest_cell(index, x, y);
+			}
+		}
+		df
+	}
+}
+
+/// Implementation of the From trait.
+/// The resulting field contains the signed chamfer distances in cells
+/// (the weighted distances divided by the weight of a horizontal step).
+impl From<&ChamferField> for ScalarField {
+	fn from(field: &ChamferField) -> Self {
+		let unit = field.mask.unit() as f64;
+		let data = field.distances.iter()
+			.zip(field.layers.iter())
+			.map(|(distance, is_foreground)| {
+				let layer = if *is_foreground { CellLayer::Foreground } else { CellLayer::Background };
+				let distance = if *distance == NO_DISTANCE { 0.0 } else { *distance as f64 / unit };
+				signed_distance(&layer, distance)
+			})
+			.collect();
+		ScalarField::new(data, field.width, field.height)
+	}
+}
+
+/// Implementation of the From trait.
+/// The result contains the weighted integer distances as a single unsigned 32 bit channel
+/// (zero for cells without a nearest cell), like the distances of the legacy tools.
+impl From<&ChamferField> for DistanceTransformationResult {
+	fn from(field: &ChamferField) -> Self {
+		let data: Vec<u32> = field.distances.iter()
+			.map(|distance| if *distance == NO_DISTANCE { 0 } else { *distance })
+			.collect();
+		DistanceTransformationResult {
+			width: field.width,
+			height: field.height,
+			spacing: Spacing::default(),
+			num_channels: 1,
+			data_type: ChannelDataType::UnsignedInt,
+			bit_depth: ChannelBitDepth::ThirtyTwo,
+			data: data.serialize_to_bytes(),
+		}
+	}
+}
@@ -0,0 +1,160 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{CellLayer, CellPosition, NO_NEAREST_CELL, unpack_coordinates};
+use crate::data::input::{InputField, Spacing, check_dimensions_and_buffer_size};
+use crate::data::scalar::{ScalarField, signed_distance};
+use crate::data::serialize::ByteSerializer;
+use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDepth};
+
+/// An input field with a third class of cells: obstacles.
+/// Obstacle cells belong to neither layer, distances travel around them instead of through them
+/// (e.g. walls in a floor plan). The layer of an obstacle cell in the input field is ignored.
+pub struct ObstacleInputField {
+	pub field: InputField,
+	pub obstacles: BitVec,
+}
+
+impl ObstacleInputField {
+	pub fn new(field: InputField, obstacles: BitVec) -> Self {
+		check_dimensions_and_buffer_size(field.width, field.height, obstacles.len());
+		ObstacleInputField {
+			field,
+			obstacles,
+		}
+	}
+}
+
+/// The octile geodesic distance of every cell to the nearest cell of the other layer,
+/// i.e. the length of the shortest path (with horizontal, vertical and diagonal steps)
+/// that does not pass through obstacle cells. The length is measured in physical units (see `Spacing`),
+/// the spacing of the input field is kept. Every cell also knows its predecessor, the next cell on this path.
+///
+/// **The distances are octile distances, not euclidean distances.** A path only consists of steps to
+/// the eight neighbours, so even without obstacles the distance to a cell five cells to the right and
+/// one cell down is 4 + √2 ≈ 5.41 instead of √26 ≈ 5.10 (up to 8 % longer). The arrival times of the
+/// `FastMarchingProcessor` with a speed of zero for obstacles are closer to the euclidean distance,
+/// but they have no predecessors.
+pub struct OctileGeodesicField {
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
+	layers: BitVec,
+	obstacles: BitVec,
+	distances: Vec<f64>,
+	predecessors: Vec<u64>,
+}
+
+impl OctileGeodesicField {
+	/// Create the field from the distances and the packed coordinates of the predecessors of all cells.
+	/// Cells without a path to the other layer have an infinite distance and no predecessor.
+	pub(crate) fn new(width: u32, height: u32, spacing: Spacing, layers: BitVec, obstacles: BitVec, distances: Vec<f64>, predecessors: Vec<u64>) -> Self {
+		OctileGeodesicField {
+			width,
+			height,
+			spacing,
+			layers,
+			obstacles,
+			distances,
+			predecessors,
+		}
+	}
+
+	pub fn layer(&self, x: u32, y: u32) -> CellLayer {
+		if self.layers[self.index(x, y)] {
+			CellLayer::Foreground
+		} else {
+			CellLayer::Background
+		}
+	}
+
+	pub fn is_obstacle(&self, x: u32, y: u32) -> bool {
+		self.obstacles[self.index(x, y)]
+	}
+
+	/// The octile geodesic distance to the nearest cell of the other layer (in physical units).
+	/// This is `None` for obstacles and for cells without a path to the other layer.
+	pub fn distance(&self, x: u32, y: u32) -> Option<f64> {
+		let distance = self.distances[self.index(x, y)];
+		if distance.is_finite() {
+			Some(distance)
+		} else {
+			None
+		}
+	}
+
+	/// The next cell on the shortest path to the other layer.
+	/// Following the predecessors leads to the nearest cell of the other layer.
+	pub fn predecessor(&self, x: u32, y: u32) -> Option<CellPosition> {
+		match self.predecessors[self.index(x, y)] {
+			NO_NEAREST_CELL => None,
+			packed => {
+				let (x, y) = unpack_coordinates(packed);
+				Some(CellPosition {
+					x,
+					y,
+					index: x as u64 + y as u64 * self.width as u64,
+				})
+			}
+		}
+	}
+
+	/// The whole shortest path from the given cell to the nearest cell of the other layer
+	/// (including both cells). The path is empty for cells without a path.
+	pub fn path(&self, x: u32, y: u32) -> Vec<CellPosition> {
+		let is_foreground = self.layers[self.index(x, y)];
+		let mut path = Vec::new();
+		let mut next = self.predecessor(x, y);
+		if next.is_some() {
+			path.push(CellPosition { x, y, index: x as u64 + y as u64 * self.width as u64 });
+		}
+		// the path ends at the first cell of the other layer (which has a predecessor of its own)
+		while let Some(position) = next {
+			path.push(position);
+			next = if self.layers[position.index as usize] == is_foreground {
+				self.predecessor(position.x, position.y)
+			} else {
+				None
+			};
+		}
+		path
+	}
+
+	fn index(&self, x: u32, y: u32) -> usize {
+		x as usize + y as usize * self.width as usize
+	}
+}
+
+/// Implementation of the From trait.
+/// The resulting field contains the signed geodesic distance of every cell (negative for foreground cells).
+/// Obstacles and cells without a path to the other layer have an infinite distance.
+impl From<&OctileGeodesicField> for ScalarField {
+	fn from(field: &OctileGeodesicField) -> Self {
+		let data = field.distances.iter()
+			.zip(field.layers.iter())
+			.map(|(distance, is_foreground)| {
+				let layer = if *is_foreground { CellLayer::Foreground } else { CellLayer::Background };
+				signed_distance(&layer, *distance)
+			})
+			.collect();
+		ScalarField::new(data, field.width, field.height)
+	}
+}
+
+/// Implementation of the From trait.
+/// The result contains the octile geodesic distance of every cell as a single 64 bit float channel,
+/// like the euclidean distance of a `DistanceTransformation`, so it can be exported the same way
+/// (with the spacing of the field).
+/// Obstacles and cells without a path to the other layer have an infinite distance.
+impl From<&OctileGeodesicField> for DistanceTransformationResult {
+	fn from(field: &OctileGeodesicField) -> Self {
+		DistanceTransformationResult {
+			width: field.width,
+			height: field.height,
+			spacing: field.spacing,
+			num_channels: 1,
+			data_type: ChannelDataType::Float,
+			bit_depth: ChannelBitDepth::SixtyFour,
+			data: field.distances.serialize_to_bytes(),
+		}
+	}
+}
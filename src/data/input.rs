@@ -5,21 +5,66 @@ use bitvec::vec::{BitVec};
 /// The number of items inside the data block match the dimension of the input.
 /// A set bit in the data block specifies a foreground cell, whereas an unset bit
 /// specifies a background cell.
+/// The spacing defines the physical size of the cells, so distances are measured in physical units.
 #[derive(Debug)]
 pub struct InputField {
     pub data: BitVec,
-    pub width: u16,
-    pub height: u16,
+    pub width: u32,
+    pub height: u32,
+    pub spacing: Spacing,
+}
+
+/// The physical size of a cell (e.g. in millimetres), i.e. the distance between the centers
+/// of two neighbouring cells on the x- and on the y-axis.
+/// The default spacing is one on both axes, so distances are measured in cells.
+/// Png files store the spacing in pixels per metre, it is converted to millimetres
+/// (see `PngInput::physical_spacing` and `PngOutput::spacing`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacing {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Spacing {
+    pub fn new(x: f64, y: f64) -> Self {
+        if !(x > 0.0 && x.is_finite() && y > 0.0 && y.is_finite()) {
+            panic!("spacing must be positive and finite (got {}x{})", x, y);
+        }
+        Spacing {
+            x,
+            y,
+        }
+    }
+
+    /// True, if the cells are squares of size one (distances are measured in cells).
+    pub fn is_unit(&self) -> bool {
+        *self == Spacing::default()
+    }
+
+    /// The squared physical distance of a vector of cells.
+    pub fn distance_squared(&self, dx: u32, dy: u32) -> f64 {
+        let (dx, dy) = (dx as f64 * self.x, dy as f64 * self.y);
+        dx * dx + dy * dy
+    }
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Spacing {
+            x: 1.0,
+            y: 1.0,
+        }
+    }
 }
 
 struct DimensionalVector<T> {
     data: Vec<T>,
-    pub width: u16,
-    pub height: u16,
+    pub width: u32,
+    pub height: u32,
 }
 
 impl<T> DimensionalVector<T> {
-    pub fn new(data: Vec<T>, width: u16, height: u16) -> Self {
+    pub fn new(data: Vec<T>, width: u32, height: u32) -> Self {
         check_dimensions_and_buffer_size(width, height, data.len());
         DimensionalVector {
             data,
@@ -38,7 +83,7 @@ pub struct ByteInputData {
 }
 
 impl ByteInputData {
-    pub fn new(buffer: Vec<u8>, threshold: u8, width: u16, height: u16) -> Self {
+    pub fn new(buffer: Vec<u8>, threshold: u8, width: u32, height: u32) -> Self {
         Self {
             buffer: DimensionalVector::new(buffer, width, height),
             threshold,
@@ -51,7 +96,7 @@ pub struct BoolInputData {
 }
 
 impl BoolInputData {
-    pub fn new(buffer: Vec<bool>, width: u16, height: u16) -> Self {
+    pub fn new(buffer: Vec<bool>, width: u32, height: u32) -> Self {
         Self {
             buffer: DimensionalVector::new(buffer, width, height),
         }
@@ -78,31 +123,51 @@ impl From<BoolInputData> for InputField {
 
 /// Implementation of the InputField type.
 impl InputField {
-    pub fn new(data: BitVec, width: u16, height: u16) -> Self {
+    pub fn new(data: BitVec, width: u32, height: u32) -> Self {
         check_dimensions_and_buffer_size(width, height, data.len());
         InputField {
             data,
             width,
             height,
+            spacing: Spacing::default(),
         }
     }
 
+    /// The physical size of the cells, the default is one on both axes.
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
     pub fn invert(&mut self) {
         self.data.iter_mut().for_each(|mut i| *i = !*i);
     }
 }
 
+/// The maximum number of cells of a field.
+/// Cells are indexed with 64 bit, so the only limit is the number of bits a `BitVec` can address
+/// (2^61 - 1 on 64 bit targets). E.g. masks of 70.000 x 70.000 pixels are supported.
+pub const MAX_CELLS: u64 = (usize::MAX >> 3) as u64;
+
+/// Checks if a field with the given dimensions can be represented (see `MAX_CELLS`).
+pub fn is_supported_size(width: u32, height: u32) -> bool {
+    width as u64 * height as u64 <= MAX_CELLS
+}
+
 /// Helper method, that checks if the length of a buffer is equal to its width times its height.
-/// The method will panic, if this is not the case.
-fn check_dimensions_and_buffer_size(width: u16, height: u16, buffer_len: usize) {
+/// The method will panic, if this is not the case or if the field has more than `MAX_CELLS` cells.
+pub(crate) fn check_dimensions_and_buffer_size(width: u32, height: u32, buffer_len: usize) {
     if width == 0 {
         panic!("width must be greater than zero"); // maybe an error type "incorrect dimensions" would be better here!
     }
     if height == 0 {
         panic!("height must be greater than zero"); // maybe an error type "incorrect dimensions" would be better here!
     }
-    let size = (width as usize * height as usize);
-    if buffer_len != size {
-        panic!("buffer size should be {}", width * height);
+    if !is_supported_size(width, height) {
+        panic!("a field of {}x{} cells exceeds the maximum of {} cells", width, height, MAX_CELLS);
+    }
+    let size = width as u64 * height as u64;
+    if buffer_len as u64 != size {
+        panic!("buffer size should be {}", size);
     }
 }
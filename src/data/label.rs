@@ -0,0 +1,81 @@
+use crate::data::{Cell, CellPosition};
+use crate::data::input::check_dimensions_and_buffer_size;
+use crate::data::scalar::ScalarField;
+
+/// A two-dimensional field of labeled seed cells.
+/// Every cell is either a seed with a label or an empty cell (`None`).
+/// Different seed regions may share the same label, e.g. all cells of one object.
+pub struct LabelInputField {
+	pub data: Vec<Option<u32>>,
+	pub width: u32,
+	pub height: u32,
+}
+
+impl LabelInputField {
+	pub fn new(data: Vec<Option<u32>>, width: u32, height: u32) -> Self {
+		check_dimensions_and_buffer_size(width, height, data.len());
+		LabelInputField {
+			data,
+			width,
+			height,
+		}
+	}
+}
+
+/// A single cell of a label field.
+#[derive(Debug, Clone)]
+pub struct LabeledCell {
+	/// The label of the nearest seed cell.
+	/// This is `None`, if the field has no seed cells at all.
+	pub label: Option<u32>,
+
+	/// True, if the cell is a seed cell itself.
+	pub is_seed: bool,
+
+	pub x: u32,
+	pub y: u32,
+
+	/// The position of the nearest seed cell (a seed cell is its own nearest seed cell).
+	pub nearest_seed_position: Option<CellPosition>,
+}
+
+impl LabeledCell {
+	/// The squared distance to the nearest seed cell.
+	pub fn distance_to_nearest_squared(&self) -> Option<u64> {
+		self.nearest_seed_position.map(|nearest| Cell::get_distance_squared(&self.x, &self.y, &nearest.x, &nearest.y))
+	}
+
+	/// The euclidean distance to the nearest seed cell.
+	pub fn distance(&self) -> Option<f64> {
+		self.distance_to_nearest_squared().map(|distance| (distance as f64).sqrt())
+	}
+}
+
+/// The feature transform of a label input field.
+/// Every cell knows its nearest seed cell and the label of that seed,
+/// so the labels of all cells form a discrete Voronoi diagram of the seed regions.
+pub struct LabelField {
+	pub data: Vec<LabeledCell>,
+	pub width: u32,
+	pub height: u32,
+}
+
+impl LabelField {
+	pub fn get(&self, x: u32, y: u32) -> &LabeledCell {
+		&self.data[x as usize + y as usize * self.width as usize]
+	}
+
+	/// The label of every cell (the discrete Voronoi diagram).
+	pub fn labels(&self) -> Vec<Option<u32>> {
+		self.data.iter().map(|cell| cell.label).collect()
+	}
+
+	/// The distance of every cell to the nearest seed, i.e. the distance inside of the region of its label.
+	/// Cells without a nearest seed have an infinite distance.
+	pub fn distances(&self) -> ScalarField {
+		let data = self.data.iter()
+			.map(|cell| cell.distance().map_or(f32::INFINITY, |distance| distance as f32))
+			.collect();
+		ScalarField::new(data, self.width, self.height)
+	}
+}
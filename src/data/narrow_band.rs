@@ -0,0 +1,108 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{CellLayer, CellPosition, DistanceField};
+use crate::data::input::Spacing;
+use crate::data::sampling::FieldSampler;
+use crate::data::scalar::{ScalarField, signed_distance};
+
+/// A distance field that only stores the nearest cells of the cells within a maximum distance
+/// to the boundary between foreground and background (the narrow band).
+/// All other cells are "far" cells, they only store their layer (a single bit).
+pub struct NarrowBandField {
+	pub width: u32,
+	pub height: u32,
+	pub max_distance: f32,
+	pub spacing: Spacing,
+	layers: BitVec,
+	band: Vec<(u64, CellPosition)>,
+}
+
+impl NarrowBandField {
+	/// Create the field from the layers of all cells (true for foreground) and the nearest cells
+	/// of the band cells. The band must be sorted by the index of the cells.
+	pub(crate) fn new(width: u32, height: u32, max_distance: f32, spacing: Spacing, layers: BitVec, band: Vec<(u64, CellPosition)>) -> Self {
+		NarrowBandField {
+			width,
+			height,
+			max_distance,
+			spacing,
+			layers,
+			band,
+		}
+	}
+
+	/// The number of cells in the narrow band.
+	pub fn band_size(&self) -> usize {
+		self.band.len()
+	}
+
+	pub fn layer(&self, x: u32, y: u32) -> CellLayer {
+		if self.layers[self.index(x, y) as usize] {
+			CellLayer::Foreground
+		} else {
+			CellLayer::Background
+		}
+	}
+
+	/// The nearest cell of the opposite layer, this is `None` for far cells.
+	pub fn nearest_cell_position(&self, x: u32, y: u32) -> Option<CellPosition> {
+		let index = self.index(x, y);
+		self.band
+			.binary_search_by_key(&index, |(band_index, _)| *band_index)
+			.ok()
+			.map(|position| self.band[position].1)
+	}
+
+	/// The euclidean distance (in physical units) to the nearest cell of the opposite layer, this is `None` for far cells.
+	pub fn distance(&self, x: u32, y: u32) -> Option<f64> {
+		self.nearest_cell_position(x, y)
+			.map(|nearest| self.spacing.distance_squared(x.abs_diff(nearest.x), y.abs_diff(nearest.y)).sqrt())
+	}
+
+	fn index(&self, x: u32, y: u32) -> u64 {
+		x as u64 + y as u64 * self.width as u64
+	}
+}
+
+/// Implementation of the From trait.
+/// Far cells have no nearest cell in the resulting distance field.
+impl From<&NarrowBandField> for DistanceField {
+	fn from(field: &NarrowBandField) -> Self {
+		let mut df = DistanceField::from_layers(field.layers.clone(), field.width, field.height);
+		df.spacing = field.spacing;
+		for (index, nearest) in field.band.iter() {
+			df.set_nearest_cell(*index as usize, nearest.x, nearest.y);
+		}
+		df
+	}
+}
+
+/// Implementation of the From trait.
+/// The resulting field contains the signed distances, far cells are clamped to the maximum distance.
+impl From<&NarrowBandField> for ScalarField {
+	fn from(field: &NarrowBandField) -> Self {
+		let mut data = Vec::with_capacity(field.layers.len());
+		for y in 0..field.height {
+			for x in 0..field.width {
+				data.push(field.value_at(x, y));
+			}
+		}
+		ScalarField::new(data, field.width, field.height)
+	}
+}
+
+/// The sampled values are signed distances, far cells are clamped to the maximum distance.
+impl FieldSampler for NarrowBandField {
+	fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn height(&self) -> u32 {
+		self.height
+	}
+
+	fn value_at(&self, x: u32, y: u32) -> f32 {
+		let distance = self.distance(x, y).unwrap_or(self.max_distance as f64);
+		signed_distance(&self.layer(x, y), distance)
+	}
+}
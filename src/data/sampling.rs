@@ -0,0 +1,173 @@
+//! Query functionality for distance fields.
+//! Positions are given in cell units, where integer coordinates hit the center of a cell.
+//! Positions outside of the field are clamped to the border cells.
+
+use crate::data::DistanceField;
+use crate::data::input::Spacing;
+use crate::data::scalar::{ScalarField, signed_distance};
+use crate::distance::euclid::EuclideanDistance;
+
+/// The interpolation method used to sample values between cell centers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+	/// Linear interpolation between the four surrounding cells.
+	Bilinear,
+
+	/// Cubic (Catmull-Rom) interpolation between the sixteen surrounding cells.
+	Bicubic,
+}
+
+/// Type for a field that can be sampled at arbitrary (sub-cell) positions.
+/// Implementors only have to provide the value at a cell, interpolation,
+/// gradients and normals are derived from that.
+pub trait FieldSampler {
+	fn width(&self) -> u32;
+
+	fn height(&self) -> u32;
+
+	/// The value of the cell at the given position.
+	fn value_at(&self, x: u32, y: u32) -> f32;
+
+	/// The physical size of the cells (see `Spacing`), the default is one on both axes.
+	fn spacing(&self) -> Spacing {
+		Spacing::default()
+	}
+
+	/// Sample the field at the given position with bilinear interpolation.
+	fn sample(&self, x: f32, y: f32) -> f32 {
+		self.sample_interpolated(x, y, Interpolation::Bilinear)
+	}
+
+	/// Sample the field at the given position with the given interpolation method.
+	fn sample_interpolated(&self, x: f32, y: f32, interpolation: Interpolation) -> f32 {
+		let x0 = x.floor();
+		let y0 = y.floor();
+		let tx = x - x0;
+		let ty = y - y0;
+		let (x0, y0) = (x0 as i64, y0 as i64);
+		match interpolation {
+			Interpolation::Bilinear => {
+				let top = lerp(self.clamped_value_at(x0, y0), self.clamped_value_at(x0 + 1, y0), tx);
+				let bottom = lerp(self.clamped_value_at(x0, y0 + 1), self.clamped_value_at(x0 + 1, y0 + 1), tx);
+				lerp(top, bottom, ty)
+			}
+			Interpolation::Bicubic => {
+				let mut rows = [0f32; 4];
+				for (row, value) in rows.iter_mut().enumerate() {
+					let cy = y0 - 1 + row as i64;
+					*value = catmull_rom(self.clamped_value_at(x0 - 1, cy),
+										 self.clamped_value_at(x0, cy),
+										 self.clamped_value_at(x0 + 1, cy),
+										 self.clamped_value_at(x0 + 2, cy),
+										 tx);
+				}
+				catmull_rom(rows[0], rows[1], rows[2], rows[3], ty)
+			}
+		}
+	}
+
+	/// The gradient (x, y) of the field at the given position, i.e. the change of the value per physical unit
+	/// (the change per cell divided by the spacing). It is approximated by the central difference of the
+	/// bilinear samples one cell apart.
+	fn gradient(&self, x: f32, y: f32) -> (f32, f32) {
+		let spacing = self.spacing();
+		let dx = (self.sample(x + 1.0, y) - self.sample(x - 1.0, y)) / (2.0 * spacing.x as f32);
+		let dy = (self.sample(x, y + 1.0) - self.sample(x, y - 1.0)) / (2.0 * spacing.y as f32);
+		(dx, dy)
+	}
+
+	/// The normalized gradient of the field at the given position.
+	/// This is (0, 0) if the gradient vanishes.
+	fn normal(&self, x: f32, y: f32) -> (f32, f32) {
+		let (dx, dy) = self.gradient(x, y);
+		let length = (dx * dx + dy * dy).sqrt();
+		if length > 0.0 {
+			(dx / length, dy / length)
+		} else {
+			(0.0, 0.0)
+		}
+	}
+
+	/// The value of the cell at the given position, clamped to the borders of the field.
+	fn clamped_value_at(&self, x: i64, y: i64) -> f32 {
+		let x = x.max(0).min(self.width() as i64 - 1);
+		let y = y.max(0).min(self.height() as i64 - 1);
+		self.value_at(x as u32, y as u32)
+	}
+}
+
+/// The sampled values of a distance field are signed euclidean distances (in physical units, see `Spacing`).
+/// Foreground cells have negative, background cells positive distances.
+impl FieldSampler for DistanceField {
+	fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn height(&self) -> u32 {
+		self.height
+	}
+
+	fn value_at(&self, x: u32, y: u32) -> f32 {
+		let cell = self.get(x, y);
+		signed_distance(&cell.layer, EuclideanDistance::calculate_physical(&cell, &self.spacing))
+	}
+
+	fn spacing(&self) -> Spacing {
+		self.spacing
+	}
+}
+
+impl FieldSampler for ScalarField {
+	fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn height(&self) -> u32 {
+		self.height
+	}
+
+	fn value_at(&self, x: u32, y: u32) -> f32 {
+		self.get(x, y)
+	}
+
+	fn spacing(&self) -> Spacing {
+		self.spacing
+	}
+}
+
+impl DistanceField {
+	/// The nearest point on the boundary between foreground and background for the given position.
+	/// The boundary is assumed to lie half a cell in front of the nearest cell of the opposite layer.
+	/// This is `None`, if the cell at the given position has no nearest cell.
+	pub fn nearest_boundary_point(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+		let cx = (x.round() as i64).max(0).min(self.width as i64 - 1);
+		let cy = (y.round() as i64).max(0).min(self.height as i64 - 1);
+		let cell = self.get(cx as u32, cy as u32);
+		let (vx, vy) = cell.nearest_vector()?;
+
+		// the position of the nearest cell (measured relative to the cell itself)
+		let nx = cx as f32 + vx as f32;
+		let ny = cy as f32 + vy as f32;
+
+		let (dx, dy) = (x - nx, y - ny);
+		let length = (dx * dx + dy * dy).sqrt();
+		if length > 0.0 {
+			Some((nx + dx / length * 0.5, ny + dy / length * 0.5))
+		} else {
+			Some((nx, ny))
+		}
+	}
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+	a + (b - a) * t
+}
+
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+	let t2 = t * t;
+	let t3 = t2 * t;
+	0.5 * ((2.0 * p1)
+		+ (-p0 + p2) * t
+		+ (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+		+ (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * t3)
+}
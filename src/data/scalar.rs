@@ -0,0 +1,133 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{DistanceField, CellLayer};
+use crate::data::input::{InputField, Spacing, check_dimensions_and_buffer_size};
+use crate::distance::euclid::EuclideanDistance;
+use crate::data::serialize::ByteSerializer;
+use crate::data::transformation::{TransformationData, TransformationResult};
+use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDepth};
+use crate::utils::f32_to_u16_clamped;
+
+/// A two-dimensional field with a single floating point value per cell.
+/// This is the decoded counterpart of a distance field and is used wherever
+/// plain distance values (instead of nearest cells) are needed.
+/// The spacing is the physical size of the cells, the distances of a distance field keep its spacing.
+#[derive(Debug, Clone)]
+pub struct ScalarField {
+	pub data: Vec<f32>,
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
+}
+
+impl ScalarField {
+	pub fn new(data: Vec<f32>, width: u32, height: u32) -> Self {
+		check_dimensions_and_buffer_size(width, height, data.len());
+		ScalarField {
+			data,
+			width,
+			height,
+			spacing: Spacing::default(),
+		}
+	}
+
+	/// The physical size of the cells, the default is one on both axes.
+	pub fn spacing(mut self, spacing: Spacing) -> Self {
+		self.spacing = spacing;
+		self
+	}
+
+	/// The value of the cell at the given position.
+	pub fn get(&self, x: u32, y: u32) -> f32 {
+		self.data[x as usize + y as usize * self.width as usize]
+	}
+}
+
+/// Implementation of the From trait.
+/// The resulting field contains the signed euclidean distance of every cell (in physical units, see `Spacing`).
+/// Distances of foreground cells are negative, distances of background cells are positive,
+/// so the boundary between both layers is the zero crossing of the field.
+impl From<&DistanceField> for ScalarField {
+	fn from(df: &DistanceField) -> Self {
+		let data = df.cells().map(|cell| signed_distance(&cell.layer, EuclideanDistance::calculate_physical(&cell, &df.spacing))).collect();
+		ScalarField::new(data, df.width, df.height).spacing(df.spacing)
+	}
+}
+
+/// Implementation of the From trait.
+/// All cells with a negative value (the inside of a signed field) define the foreground.
+/// The input field keeps the spacing of the field.
+impl From<&ScalarField> for InputField {
+	fn from(field: &ScalarField) -> Self {
+		let mut data = BitVec::new();
+		field.data.iter().for_each(|value| data.push(*value < 0.0));
+		InputField::new(data, field.width, field.height).spacing(field.spacing)
+	}
+}
+
+/// Implementation of the From trait.
+/// The resulting field contains the decoded values of the first channel of the result.
+impl From<&DistanceTransformationResult> for ScalarField {
+	fn from(result: &DistanceTransformationResult) -> Self {
+		let num_bytes = result.bit_depth.number_of_bytes() as usize;
+		let stride = num_bytes * result.num_channels as usize;
+		let data = result.data.chunks_exact(stride)
+			.map(|chunk| decode_value(&chunk[..num_bytes], &result.data_type, &result.bit_depth))
+			.collect();
+		ScalarField::new(data, result.width, result.height).spacing(result.spacing)
+	}
+}
+
+/// Implementation of the From trait.
+/// The result contains the values of the field as a single 32 bit float channel,
+/// e.g. to export arrival times or other distances that are not based on nearest cells.
+impl From<&ScalarField> for DistanceTransformationResult {
+	fn from(field: &ScalarField) -> Self {
+		DistanceTransformationResult {
+			width: field.width,
+			height: field.height,
+			spacing: field.spacing,
+			num_channels: 1,
+			data_type: ChannelDataType::Float,
+			bit_depth: ChannelBitDepth::ThirtyTwo,
+			data: field.data.serialize_to_bytes(),
+		}
+	}
+}
+
+/// Implementation of the From trait.
+/// The result contains the values of the field as a single 16 bit channel, like the legacy u16 distances
+/// (see `TransformOutputGenerator`): the values are truncated and clamped to 0 - 65535, so infinite values
+/// (e.g. the arrival times of unreachable cells) become 65535.
+impl From<&ScalarField> for TransformationResult<u16> {
+	fn from(field: &ScalarField) -> Self {
+		let data = field.data.iter().map(|value| f32_to_u16_clamped(value.max(0.0))).collect();
+		TransformationResult::OneDimensional(TransformationData::new(field.width, field.height, data).spacing(field.spacing))
+	}
+}
+
+/// Decodes a single little endian value.
+fn decode_value(bytes: &[u8], data_type: &ChannelDataType, bit_depth: &ChannelBitDepth) -> f32 {
+	let mut buffer = [0u8; 8];
+	buffer[..bytes.len()].copy_from_slice(bytes);
+	match (data_type, bit_depth) {
+		(ChannelDataType::UnsignedInt, ChannelBitDepth::Eight) => bytes[0] as f32,
+		(ChannelDataType::UnsignedInt, ChannelBitDepth::Sixteen) => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+		(ChannelDataType::UnsignedInt, ChannelBitDepth::ThirtyTwo) => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+		(ChannelDataType::UnsignedInt, ChannelBitDepth::SixtyFour) => u64::from_le_bytes(buffer) as f32,
+		(ChannelDataType::SignedInt, ChannelBitDepth::Eight) => bytes[0] as i8 as f32,
+		(ChannelDataType::SignedInt, ChannelBitDepth::Sixteen) => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+		(ChannelDataType::SignedInt, ChannelBitDepth::ThirtyTwo) => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+		(ChannelDataType::SignedInt, ChannelBitDepth::SixtyFour) => i64::from_le_bytes(buffer) as f32,
+		(ChannelDataType::Float, ChannelBitDepth::ThirtyTwo) => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+		(ChannelDataType::Float, ChannelBitDepth::SixtyFour) => f64::from_le_bytes(buffer) as f32,
+		(ChannelDataType::Float, _) => panic!("floating point values must have 32 or 64 bits"),
+	}
+}
+
+pub(crate) fn signed_distance(layer: &CellLayer, distance: f64) -> f32 {
+	match layer {
+		CellLayer::Foreground => -distance as f32,
+		CellLayer::Background => distance as f32,
+	}
+}
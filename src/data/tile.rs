@@ -0,0 +1,210 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use bitvec::vec::BitVec;
+
+use crate::data::input::{InputField, Spacing};
+use crate::data::scalar::ScalarField;
+
+/// A source of input cells that can be read region by region,
+/// so that fields larger than the available memory can be processed.
+pub trait TileSource {
+	fn width(&self) -> u32;
+	fn height(&self) -> u32;
+
+	/// The physical size of the cells, the default is one on both axes.
+	fn spacing(&self) -> Spacing {
+		Spacing::default()
+	}
+
+	/// Read the cells of the given region as a separate input field.
+	fn read_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> io::Result<InputField>;
+}
+
+/// A destination for distance values that is written region by region.
+pub trait TileSink {
+	/// Write the values of the region with its top left corner at the given position.
+	fn write_region(&mut self, x: u32, y: u32, region: &ScalarField) -> io::Result<()>;
+}
+
+impl TileSource for InputField {
+	fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn height(&self) -> u32 {
+		self.height
+	}
+
+	fn spacing(&self) -> Spacing {
+		self.spacing
+	}
+
+	fn read_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> io::Result<InputField> {
+		check_region(self.width, self.height, x, y, width, height);
+		let mut data = BitVec::with_capacity(width as usize * height as usize);
+		for row in y as usize..(y + height) as usize {
+			let start = x as usize + row * self.width as usize;
+			for column in start..start + width as usize {
+				data.push(self.data[column]);
+			}
+		}
+		Ok(InputField::new(data, width, height).spacing(self.spacing))
+	}
+}
+
+impl TileSink for ScalarField {
+	fn write_region(&mut self, x: u32, y: u32, region: &ScalarField) -> io::Result<()> {
+		check_region(self.width, self.height, x, y, region.width, region.height);
+		for row in 0..region.height as usize {
+			let start = x as usize + (y as usize + row) * self.width as usize;
+			let source = row * region.width as usize;
+			self.data[start..start + region.width as usize].copy_from_slice(&region.data[source..source + region.width as usize]);
+		}
+		Ok(())
+	}
+}
+
+/// An input field stored in a raw file with one byte per cell (row by row, without a header).
+/// Cells with a value other than zero are foreground cells.
+/// Only the rows of a requested region are read from the file.
+pub struct RawInputFile {
+	file: File,
+	width: u32,
+	height: u32,
+	spacing: Spacing,
+}
+
+impl RawInputFile {
+	pub fn open<P: AsRef<Path>>(path: P, width: u32, height: u32) -> io::Result<Self> {
+		let file = File::open(path)?;
+		check_file_size(&file, width, height, 1)?;
+		Ok(RawInputFile {
+			file,
+			width,
+			height,
+			spacing: Spacing::default(),
+		})
+	}
+
+	/// The physical size of the cells, the default is one on both axes.
+	pub fn spacing(mut self, spacing: Spacing) -> Self {
+		self.spacing = spacing;
+		self
+	}
+}
+
+impl TileSource for RawInputFile {
+	fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn height(&self) -> u32 {
+		self.height
+	}
+
+	fn spacing(&self) -> Spacing {
+		self.spacing
+	}
+
+	fn read_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> io::Result<InputField> {
+		check_region(self.width, self.height, x, y, width, height);
+		let mut data = BitVec::with_capacity(width as usize * height as usize);
+		let mut row_buffer = vec![0u8; width as usize];
+		for row in y as u64..(y + height) as u64 {
+			self.file.seek(SeekFrom::Start(x as u64 + row * self.width as u64))?;
+			self.file.read_exact(&mut row_buffer)?;
+			for value in row_buffer.iter() {
+				data.push(*value != 0);
+			}
+		}
+		Ok(InputField::new(data, width, height).spacing(self.spacing))
+	}
+}
+
+/// A scalar field stored in a raw file with one little endian 32 bit float per cell
+/// (row by row, without a header).
+/// Regions are read from and written to the file directly, so the field is never held in memory.
+pub struct RawScalarFile {
+	file: File,
+	width: u32,
+	height: u32,
+}
+
+impl RawScalarFile {
+	/// Create a new file (or truncate an existing one) with all values set to zero.
+	pub fn create<P: AsRef<Path>>(path: P, width: u32, height: u32) -> io::Result<Self> {
+		let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+		file.set_len(width as u64 * height as u64 * 4)?;
+		Ok(RawScalarFile {
+			file,
+			width,
+			height,
+		})
+	}
+
+	pub fn open<P: AsRef<Path>>(path: P, width: u32, height: u32) -> io::Result<Self> {
+		let file = OpenOptions::new().read(true).write(true).open(path)?;
+		check_file_size(&file, width, height, 4)?;
+		Ok(RawScalarFile {
+			file,
+			width,
+			height,
+		})
+	}
+
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	pub fn read_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> io::Result<ScalarField> {
+		check_region(self.width, self.height, x, y, width, height);
+		let mut data = Vec::with_capacity(width as usize * height as usize);
+		let mut row_buffer = vec![0u8; width as usize * 4];
+		for row in y as u64..(y + height) as u64 {
+			self.file.seek(SeekFrom::Start((x as u64 + row * self.width as u64) * 4))?;
+			self.file.read_exact(&mut row_buffer)?;
+			data.extend(row_buffer.chunks_exact(4).map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])));
+		}
+		Ok(ScalarField::new(data, width, height))
+	}
+}
+
+impl TileSink for RawScalarFile {
+	fn write_region(&mut self, x: u32, y: u32, region: &ScalarField) -> io::Result<()> {
+		check_region(self.width, self.height, x, y, region.width, region.height);
+		let mut row_buffer = Vec::with_capacity(region.width as usize * 4);
+		for (row, values) in region.data.chunks_exact(region.width as usize).enumerate() {
+			row_buffer.clear();
+			for value in values {
+				row_buffer.extend_from_slice(&value.to_le_bytes());
+			}
+			self.file.seek(SeekFrom::Start((x as u64 + (y as u64 + row as u64) * self.width as u64) * 4))?;
+			self.file.write_all(&row_buffer)?;
+		}
+		Ok(())
+	}
+}
+
+/// Panics, if the region is not inside of the field (the sums are calculated without overflow).
+pub(crate) fn check_region(width: u32, height: u32, x: u32, y: u32, region_width: u32, region_height: u32) {
+	if x as u64 + region_width as u64 > width as u64 || y as u64 + region_height as u64 > height as u64 {
+		panic!("region {}x{} at ({}, {}) exceeds the field of size {}x{}", region_width, region_height, x, y, width, height);
+	}
+}
+
+fn check_file_size(file: &File, width: u32, height: u32, bytes_per_cell: u64) -> io::Result<()> {
+	let expected = width as u64 * height as u64 * bytes_per_cell;
+	let actual = file.metadata()?.len();
+	if actual != expected {
+		return Err(io::Error::new(io::ErrorKind::InvalidData,
+								  format!("file has {} bytes, but a {}x{} field needs {} bytes", actual, width, height, expected)));
+	}
+	Ok(())
+}
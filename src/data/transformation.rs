@@ -1,5 +1,6 @@
 use crate::distance::{DistanceLayer, DistanceType, OneDimensionalDistanceCalculation, TwoDimensionalDistanceCalculation};
 use crate::data::{DistanceField, Cell, CellLayer};
+use crate::data::input::Spacing;
 use crate::export::BitDepth;
 use crate::distance::euclid::{EuclideanDistance, EuclideanDistanceSquared};
 use crate::distance::cartesian::CartesianDistance;
@@ -8,6 +9,7 @@ use crate::distance::rectilinear::RectilinearDistance;
 use crate::distance::nearest_cell::{NearestCellIndex, NearestCellIndexOffset, NearestCellPosition};
 use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDepth};
 use crate::data::serialize::ByteSerializer;
+use crate::utils::{f32_to_u8_clamped, f32_to_u16_clamped, i32_to_u8_clamped, i32_to_u16_clamped};
 
 impl From<DistanceField> for DistanceTransformation {
 	fn from(df: DistanceField) -> Self {
@@ -49,10 +51,11 @@ impl DistanceTransformation {
 		self.distance_type = distance_type;
 	}
 
-	fn get_trans_result<T>(&self, width: u16, height: u16, data: Vec<T>) -> TransformationData<T> {
+	fn get_trans_result<T>(&self, width: u32, height: u32, data: Vec<T>) -> TransformationData<T> {
 		TransformationData::<T> {
 			width,
 			height,
+			spacing: self.distance_field.spacing,
 			data,
 		}
 	}
@@ -68,27 +71,36 @@ pub enum TransformationResult<T> {
 }
 
 pub struct TransformationData<T> {
-	pub width: u16,
-	pub height: u16,
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
 	pub data: Vec<T>,
 	// length of the data vector should be equal width * height
 }
 
 pub struct DataDescriptor {
-	pub width: u16,
-	pub height: u16,
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
 	pub bit_depth: BitDepth,
 	pub num_channels: u8,
 }
 
 impl<T> TransformationData<T> {
-	pub fn new(width: u16, height: u16, data: Vec<T>) -> Self {
+	pub fn new(width: u32, height: u32, data: Vec<T>) -> Self {
 		Self {
 			width,
 			height,
+			spacing: Spacing::default(),
 			data,
 		}
 	}
+
+	/// The physical size of the cells, the default is one on both axes.
+	pub fn spacing(mut self, spacing: Spacing) -> Self {
+		self.spacing = spacing;
+		self
+	}
 }
 
 pub struct DistanceTransformation {
@@ -99,19 +111,59 @@ pub struct DistanceTransformation {
 }
 
 impl DistanceTransformation {
+	/// The encoded distances of all cells. For cells that are squares of size one the integer distances
+	/// are encoded as before: the squared euclidean distance as u64, the cartesian distance as i32 pairs,
+	/// the Chebyshev distance as u16, the rectilinear distance and the nearest cell index as u32,
+	/// the index offset as i32 and the nearest cell position as u16 pairs. These types are only widened,
+	/// if the dimensions of the field exceed their range. The distances of other spacings are measured in
+	/// physical units (see `Spacing`), so they are floating point values: 64 bit for a single channel and
+	/// 32 bit for the two channels of the cartesian distance. The euclidean distance is always a 64 bit float.
 	pub fn result(&self) -> DistanceTransformationResult {
+		let spacing = self.distance_field.spacing;
+		let (width, height) = (self.distance_field.width as u64, self.distance_field.height as u64);
+		// the largest coordinate difference and the largest index of a cell
+		let max_delta = width.max(height).saturating_sub(1);
+		let max_index = (width * height).saturating_sub(1);
 		match self.distance_type {
-			DistanceType::EuclideanDistance => self.fill_buffer_f64(EuclideanDistance::calculate, 0f64),
-			DistanceType::EuclideanDistanceSquared => self.fill_buffer_u64(EuclideanDistanceSquared::calculate_u64, 0),
-
-			// DistanceType::CartesianDistance => self.fill_buffer_i32_i32(CartesianDistance::calculate_i32, (0, 0)),
+			DistanceType::EuclideanDistance =>
+				self.fill_buffer_f64(|cell| EuclideanDistance::calculate_physical(cell, &spacing), 0f64),
+			DistanceType::EuclideanDistanceSquared if spacing.is_unit() =>
+				self.fill_buffer_u64(EuclideanDistanceSquared::calculate_u64, 0),
+			DistanceType::EuclideanDistanceSquared =>
+				self.fill_buffer_f64(|cell| EuclideanDistanceSquared::calculate_physical(cell, &spacing), 0f64),
+			DistanceType::CartesianDistance if spacing.is_unit() && max_delta <= i32::MAX as u64 =>
+				self.fill_buffer_i32_i32(CartesianDistance::calculate, (0, 0)),
+			DistanceType::CartesianDistance if spacing.is_unit() =>
+				self.fill_buffer_i64_i64(|cell| cell.nearest_vector().unwrap_or((0, 0)), (0, 0)),
 			DistanceType::CartesianDistance =>
-				self.get_transformation_result(self.two_dimensional_result(CartesianDistance::calculate, (0, 0)), 2, ChannelDataType::UnsignedInt, ChannelBitDepth::ThirtyTwo),
-			DistanceType::ChebyshevDistance => self.fill_buffer_u16(ChebyshevDistance::calculate, 0),
-			DistanceType::RectilinearDistance => self.fill_buffer_u32(RectilinearDistance::calculate, 0),
-			DistanceType::NearestCellIndex => self.fill_buffer_u32(NearestCellIndex::calculate_u32, 0),
-			DistanceType::NearestCellIndexOffset => self.fill_buffer_i32(NearestCellIndexOffset::calculate_i32, 0),
-			DistanceType::NearestCellPosition => self.fill_buffer_u16_u16(NearestCellPosition::calculate, (0, 0)),
+				self.fill_buffer_f32_f32(|cell| {
+					let (dx, dy) = CartesianDistance::calculate_physical(cell, &spacing);
+					(dx as f32, dy as f32)
+				}, (0f32, 0f32)),
+			DistanceType::ChebyshevDistance if spacing.is_unit() && max_delta <= u16::MAX as u64 =>
+				self.fill_buffer_u16(|cell| ChebyshevDistance::calculate(cell) as u16, 0),
+			DistanceType::ChebyshevDistance if spacing.is_unit() =>
+				self.fill_buffer_u32(ChebyshevDistance::calculate, 0),
+			DistanceType::ChebyshevDistance =>
+				self.fill_buffer_f64(|cell| ChebyshevDistance::calculate_physical(cell, &spacing), 0f64),
+			DistanceType::RectilinearDistance if spacing.is_unit() && 2 * max_delta <= u32::MAX as u64 =>
+				self.fill_buffer_u32(RectilinearDistance::calculate, 0),
+			DistanceType::RectilinearDistance if spacing.is_unit() =>
+				self.fill_buffer_u64(|cell| cell.nearest_vector().map_or(0, |(dx, dy)| dx.unsigned_abs() + dy.unsigned_abs()), 0),
+			DistanceType::RectilinearDistance =>
+				self.fill_buffer_f64(|cell| RectilinearDistance::calculate_physical(cell, &spacing), 0f64),
+			DistanceType::NearestCellIndex if max_index <= u32::MAX as u64 =>
+				self.fill_buffer_u32(|cell| NearestCellIndex::calculate_u64(cell) as u32, 0),
+			DistanceType::NearestCellIndex => self.fill_buffer_u64(NearestCellIndex::calculate_u64, 0),
+			DistanceType::NearestCellIndexOffset if max_index <= i32::MAX as u64 =>
+				self.fill_buffer_i32(|cell| NearestCellIndexOffset::calculate_i64(cell) as i32, 0),
+			DistanceType::NearestCellIndexOffset => self.fill_buffer_i64(NearestCellIndexOffset::calculate_i64, 0),
+			DistanceType::NearestCellPosition if max_delta <= u16::MAX as u64 =>
+				self.fill_buffer_u16_u16(|cell| {
+					let (x, y) = NearestCellPosition::calculate(cell);
+					(x as u16, y as u16)
+				}, (0, 0)),
+			DistanceType::NearestCellPosition => self.fill_buffer_u32_u32(NearestCellPosition::calculate, (0, 0)),
 		}
 	}
 
@@ -122,36 +174,52 @@ impl DistanceTransformation {
 			bit_depth,
 			width: self.distance_field.width,
 			height: self.distance_field.height,
+			spacing: self.distance_field.spacing,
 			data: buffer.serialize_to_bytes(),
 		}
 	}
 
-	fn fill_buffer_f64(&self, function: fn(&Cell) -> f64, null_val: f64) -> DistanceTransformationResult {
+	fn fill_buffer_f64(&self, function: impl Fn(&Cell) -> f64, null_val: f64) -> DistanceTransformationResult {
 		let buffer: Vec<f64> = self.one_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 1, ChannelDataType::Float, ChannelBitDepth::SixtyFour)
 	}
 
-	fn fill_buffer_u16(&self, function: fn(&Cell) -> u16, null_val: u16) -> DistanceTransformationResult {
+	fn fill_buffer_f32_f32(&self, function: impl Fn(&Cell) -> (f32, f32), null_val: (f32, f32)) -> DistanceTransformationResult {
+		let buffer: Vec<(f32, f32)> = self.two_dimensional_result(function, null_val);
+		self.get_transformation_result(buffer, 2, ChannelDataType::Float, ChannelBitDepth::ThirtyTwo)
+	}
+
+	fn fill_buffer_u16(&self, function: impl Fn(&Cell) -> u16, null_val: u16) -> DistanceTransformationResult {
 		let buffer: Vec<u16> = self.one_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 1, ChannelDataType::UnsignedInt, ChannelBitDepth::Sixteen)
 	}
 
-	fn fill_buffer_u16_u16(&self, function: fn(&Cell) -> (u16, u16), null_val: (u16, u16)) -> DistanceTransformationResult {
+	fn fill_buffer_u16_u16(&self, function: impl Fn(&Cell) -> (u16, u16), null_val: (u16, u16)) -> DistanceTransformationResult {
 		let buffer: Vec<(u16, u16)> = self.two_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 2, ChannelDataType::UnsignedInt, ChannelBitDepth::Sixteen)
 	}
 
-	fn fill_buffer_u32(&self, function: fn(&Cell) -> u32, null_val: u32) -> DistanceTransformationResult {
+	fn fill_buffer_u32(&self, function: impl Fn(&Cell) -> u32, null_val: u32) -> DistanceTransformationResult {
 		let buffer: Vec<u32> = self.one_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 1, ChannelDataType::UnsignedInt, ChannelBitDepth::ThirtyTwo)
 	}
 
-	fn fill_buffer_i32(&self, function: fn(&Cell) -> i32, null_val: i32) -> DistanceTransformationResult {
+	fn fill_buffer_u32_u32(&self, function: fn(&Cell) -> (u32, u32), null_val: (u32, u32)) -> DistanceTransformationResult {
+		let buffer: Vec<(u32, u32)> = self.two_dimensional_result(function, null_val);
+		self.get_transformation_result(buffer, 2, ChannelDataType::UnsignedInt, ChannelBitDepth::ThirtyTwo)
+	}
+
+	fn fill_buffer_i32(&self, function: impl Fn(&Cell) -> i32, null_val: i32) -> DistanceTransformationResult {
 		let buffer: Vec<i32> = self.one_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 1, ChannelDataType::SignedInt, ChannelBitDepth::ThirtyTwo)
 	}
 
-	fn fill_buffer_u64(&self, function: fn(&Cell) -> u64, null_val: u64) -> DistanceTransformationResult {
+	fn fill_buffer_i64(&self, function: fn(&Cell) -> i64, null_val: i64) -> DistanceTransformationResult {
+		let buffer: Vec<i64> = self.one_dimensional_result(function, null_val);
+		self.get_transformation_result(buffer, 1, ChannelDataType::SignedInt, ChannelBitDepth::SixtyFour)
+	}
+
+	fn fill_buffer_u64(&self, function: impl Fn(&Cell) -> u64, null_val: u64) -> DistanceTransformationResult {
 		let buffer: Vec<u64> = self.one_dimensional_result(function, null_val);
 		self.get_transformation_result(buffer, 1, ChannelDataType::UnsignedInt, ChannelBitDepth::SixtyFour)
 	}
@@ -161,8 +229,13 @@ impl DistanceTransformation {
 		self.get_transformation_result(buffer, 2, ChannelDataType::SignedInt, ChannelBitDepth::ThirtyTwo)
 	}
 
+	fn fill_buffer_i64_i64(&self, function: impl Fn(&Cell) -> (i64, i64), null_val: (i64, i64)) -> DistanceTransformationResult {
+		let buffer: Vec<(i64, i64)> = self.two_dimensional_result(function, null_val);
+		self.get_transformation_result(buffer, 2, ChannelDataType::SignedInt, ChannelBitDepth::SixtyFour)
+	}
+
 	// fn one_dimensional_result<T: Copy>(&self, function: fn(&Cell) -> T, null_val: T) -> DistanceTransformationResult {
-	fn one_dimensional_result<T: Copy>(&self, function: fn(&Cell) -> T, null_val: T) -> Vec<T> {
+	fn one_dimensional_result<T: Copy>(&self, function: impl Fn(&Cell) -> T, null_val: T) -> Vec<T> {
 
 		// bytevec crate?
 		let mut buffer: Vec<T> = self.init_buffer();
@@ -173,14 +246,14 @@ impl DistanceTransformation {
 		};
 		match cell_filter {
 			None => {
-				self.distance_field.data.iter()
-					.for_each(|cell: &Cell| {
+				self.distance_field.cells()
+					.for_each(|cell: Cell| {
 						buffer.push(function(&cell));
 					});
 			}
 			Some(filter_value) => {
-				self.distance_field.data.iter()
-					.for_each(|cell: &Cell| {
+				self.distance_field.cells()
+					.for_each(|cell: Cell| {
 						if cell.layer == filter_value {
 							buffer.push(function(&cell));
 						} else {
@@ -192,10 +265,10 @@ impl DistanceTransformation {
 		buffer
 	}
 
-	fn two_dimensional_result<T: Copy>(&self, function: fn(&Cell) -> (T, T), null_val: (T, T)) -> Vec<(T, T)> {
+	fn two_dimensional_result<T: Copy>(&self, function: impl Fn(&Cell) -> (T, T), null_val: (T, T)) -> Vec<(T, T)> {
 		// TODO: filter implementieren !
 		let mut buffer: Vec<(T, T)> = self.init_buffer();
-		self.distance_field.data.iter().for_each(|cell: &Cell| {
+		self.distance_field.cells().for_each(|cell: Cell| {
 			buffer.push(function(&cell));
 		});
 		buffer
@@ -204,13 +277,13 @@ impl DistanceTransformation {
 	fn three_dimensional_result<T: Copy>(&self, function: fn(&Cell) -> (T, T, T), null_val: (T, T, T)) -> Vec<(T, T, T)> {
 		// TODO: filter implementieren !
 		let mut buffer: Vec<(T, T, T)> = self.init_buffer();
-		self.distance_field.data.iter().for_each(|cell: &Cell| {
+		self.distance_field.cells().for_each(|cell: Cell| {
 			buffer.push(function(&cell));
 		});
 		buffer
 	}
 
-	fn one_dimensional_distance_transform<T: Copy>(&self, function: fn(&Cell) -> T, null_val: T) -> TransformationResult<T> {
+	fn one_dimensional_distance_transform<T: Copy>(&self, function: impl Fn(&Cell) -> T, null_val: T) -> TransformationResult<T> {
 		let mut buffer: Vec<T> = self.init_buffer();
 
 		let cell_filter = match self.filter {
@@ -221,14 +294,14 @@ impl DistanceTransformation {
 
 		match cell_filter {
 			None => {
-				self.distance_field.data.iter()
-					.for_each(|cell: &Cell| {
+				self.distance_field.cells()
+					.for_each(|cell: Cell| {
 						buffer.push(function(&cell));
 					});
 			}
 			Some(filter_value) => {
-				self.distance_field.data.iter()
-					.for_each(|cell: &Cell| {
+				self.distance_field.cells()
+					.for_each(|cell: Cell| {
 						if cell.layer == filter_value {
 							buffer.push(function(&cell));
 						} else {
@@ -240,10 +313,10 @@ impl DistanceTransformation {
 		TransformationResult::OneDimensional(self.get_transformation_data(buffer))
 	}
 
-	fn two_dimensional_distance_transform<T>(&self, function: fn(&Cell) -> (T, T), null_val: (T, T)) -> TransformationResult<T> {
+	fn two_dimensional_distance_transform<T>(&self, function: impl Fn(&Cell) -> (T, T), null_val: (T, T)) -> TransformationResult<T> {
 		// TODO: filter implementieren !
 		let mut buffer: Vec<(T, T)> = self.init_buffer();
-		self.distance_field.data.iter().for_each(|cell: &Cell| {
+		self.distance_field.cells().for_each(|cell: Cell| {
 			buffer.push(function(&cell));
 		});
 		TransformationResult::TwoDimensional(self.get_transformation_data(buffer))
@@ -252,7 +325,7 @@ impl DistanceTransformation {
 	fn three_dimensional_distance_transform<T>(&self, function: fn(&Cell) -> (T, T, T), null_val: (T, T, T)) -> TransformationResult<T> {
 		// TODO: filter implementieren !
 		let mut buffer: Vec<(T, T, T)> = self.init_buffer();
-		self.distance_field.data.iter().for_each(|cell: &Cell| {
+		self.distance_field.cells().for_each(|cell: Cell| {
 			buffer.push(function(&cell));
 		});
 		TransformationResult::ThreeDimensional(self.get_transformation_data(buffer))
@@ -264,6 +337,33 @@ impl DistanceTransformation {
 
 	fn get_transformation_data<T>(&self, buffer: Vec<T>) -> TransformationData<T> {
 		TransformationData::new(self.distance_field.width, self.distance_field.height, buffer)
+			.spacing(self.distance_field.spacing)
+	}
+
+	/// The legacy transformation of the distances in physical units, if the cells are no squares of size one
+	/// (see `Spacing`). The distances are converted like the distances in cells, e.g. cartesian distances
+	/// with the signed conversion.
+	fn physical_distance_transform<T: Copy>(&self, unsigned: impl Fn(f64) -> T, signed: impl Fn(f64) -> T) -> Option<TransformationResult<T>> {
+		let spacing = self.distance_field.spacing;
+		if spacing.is_unit() {
+			return None;
+		}
+		match self.distance_type {
+			DistanceType::EuclideanDistance =>
+				Some(self.one_dimensional_distance_transform(|cell| unsigned(EuclideanDistance::calculate_physical(cell, &spacing)), unsigned(0.0))),
+			DistanceType::EuclideanDistanceSquared =>
+				Some(self.one_dimensional_distance_transform(|cell| unsigned(EuclideanDistanceSquared::calculate_physical(cell, &spacing)), unsigned(0.0))),
+			DistanceType::CartesianDistance =>
+				Some(self.two_dimensional_distance_transform(|cell| {
+					let (dx, dy) = CartesianDistance::calculate_physical(cell, &spacing);
+					(signed(dx), signed(dy))
+				}, (signed(0.0), signed(0.0)))),
+			DistanceType::ChebyshevDistance =>
+				Some(self.one_dimensional_distance_transform(|cell| unsigned(ChebyshevDistance::calculate_physical(cell, &spacing)), unsigned(0.0))),
+			DistanceType::RectilinearDistance =>
+				Some(self.one_dimensional_distance_transform(|cell| unsigned(RectilinearDistance::calculate_physical(cell, &spacing)), unsigned(0.0))),
+			_ => None,
+		}
 	}
 }
 
@@ -272,10 +372,14 @@ pub trait TransformOutputGenerator<T> {
 }
 
 // TODO: scale and filter are not taken into account atm. for TransformOutputGenerator !
+// The distances are measured in physical units, if the field has a spacing (see `Spacing`).
 // TODO: implement distance field -> TransformationData
 
 impl TransformOutputGenerator<u8> for DistanceTransformation {
 	fn transform(&self) -> TransformationResult<u8> {
+		if let Some(result) = self.physical_distance_transform(|value| f32_to_u8_clamped(value as f32), |value| i32_to_u8_clamped(value as i32)) {
+			return result;
+		}
 		match self.distance_type {
 			DistanceType::EuclideanDistance => self.one_dimensional_distance_transform(EuclideanDistance::calculate_legacy, 0),
 			DistanceType::EuclideanDistanceSquared => self.one_dimensional_distance_transform(EuclideanDistanceSquared::calculate_legacy, 0),
@@ -291,6 +395,9 @@ impl TransformOutputGenerator<u8> for DistanceTransformation {
 
 impl TransformOutputGenerator<u16> for DistanceTransformation {
 	fn transform(&self) -> TransformationResult<u16> {
+		if let Some(result) = self.physical_distance_transform(|value| f32_to_u16_clamped(value as f32), |value| i32_to_u16_clamped(value as i32)) {
+			return result;
+		}
 		match self.distance_type {
 			DistanceType::EuclideanDistance => self.one_dimensional_distance_transform(EuclideanDistance::calculate_legacy, 0),
 			DistanceType::EuclideanDistanceSquared => self.one_dimensional_distance_transform(EuclideanDistanceSquared::calculate_legacy, 0),
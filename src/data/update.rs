@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::data::DistanceField;
+use crate::data::input::{InputField, Spacing};
+use crate::distance::DistanceType;
+use crate::processor::SourceProcessor;
+
+/// The reasons why a distance field cannot be updated incrementally (see `DistanceField::update`).
+/// The field has to be processed again with its processor then.
+#[derive(Debug, PartialEq)]
+pub enum UpdateError {
+	/// The processor does not find the exact euclidean nearest cells (e.g. the `EightSideSweepProcessor`),
+	/// so the recomputed cells would not match its result.
+	NotExact,
+	/// The processor has no maximum distance, so every cell of the field could be influenced by the region.
+	NotBounded,
+	/// The spacing of the input differs from the spacing of the field.
+	SpacingChanged,
+	/// The field has an extended border (see `BoundaryProcessor`).
+	HasBorder,
+}
+
+impl fmt::Display for UpdateError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "error: {:?}", self)
+	}
+}
+
+impl DistanceField {
+	/// Update the field after the cells of the given (dirty) region of the input have been changed,
+	/// e.g. by an editor. The input must be the complete, updated input of the field and the processor
+	/// must be the processor, that generated the field.
+	///
+	/// A cell outside of the region keeps its nearest cell, if the region is farther away than this
+	/// nearest cell, since neither the nearest cell changed nor any cell of the region can be nearer.
+	/// So only the cells within the maximum distance of the processor around the region (and the cells
+	/// of the region) are searched and recomputed. They get their exact nearest cell (in physical units,
+	/// see `Spacing`), cells with the same distance are ordered by row and column like in the `NarrowBandProcessor`.
+	///
+	/// This only matches a full recompute, if the processor is exact and bounded (see `SourceProcessor::minimizes`
+	/// and `SourceProcessor::max_distance`), e.g. the `NarrowBandProcessor`. Otherwise, or if the field cannot be
+	/// updated for another reason, an error is returned and the field is not changed, so the caller can process
+	/// the input again.
+	pub fn update(&mut self, field: &InputField, processor: &dyn SourceProcessor, x: u32, y: u32, width: u32, height: u32) -> Result<(), UpdateError> {
+		if field.width != self.width || field.height != self.height {
+			panic!("input of size {}x{} does not match the field of size {}x{}", field.width, field.height, self.width, self.height);
+		}
+		if x as u64 + width as u64 > self.width as u64 || y as u64 + height as u64 > self.height as u64 {
+			panic!("region {}x{} at ({}, {}) exceeds the field of size {}x{}", width, height, x, y, self.width, self.height);
+		}
+		if !processor.minimizes(DistanceType::EuclideanDistance, &field.spacing) {
+			return Err(UpdateError::NotExact);
+		}
+		let max_distance = processor.max_distance().ok_or(UpdateError::NotBounded)?;
+		if field.spacing != self.spacing {
+			return Err(UpdateError::SpacingChanged);
+		}
+		if self.border.is_some() {
+			return Err(UpdateError::HasBorder);
+		}
+
+		// the influence radius of the region is the maximum distance of the processor
+		let max_squared = max_distance * max_distance;
+		let expand_x = (max_distance / self.spacing.x).ceil().min(self.width as f64) as u32;
+		let expand_y = (max_distance / self.spacing.y).ceil().min(self.height as f64) as u32;
+
+		// the affected cells have to be found with the nearest cells before the update
+		let w = self.width as usize;
+		let (right, bottom) = (x + width, y + height);
+		let mut affected = Vec::new();
+		for cell_y in y.saturating_sub(expand_y)..bottom.saturating_add(expand_y).min(self.height) {
+			for cell_x in x.saturating_sub(expand_x)..right.saturating_add(expand_x).min(self.width) {
+				let index = cell_x as usize + cell_y as usize * w;
+				let region_x = cell_x.max(x).min(right.saturating_sub(1));
+				let region_y = cell_y.max(y).min(bottom.saturating_sub(1));
+				let region_squared = self.spacing.distance_squared(cell_x.abs_diff(region_x), cell_y.abs_diff(region_y));
+				let influence_squared = match self.nearest_cell_position(index) {
+					Some(nearest) => self.spacing.distance_squared(cell_x.abs_diff(nearest.x), cell_y.abs_diff(nearest.y)),
+					None => max_squared,
+				};
+				if region_squared <= influence_squared {
+					affected.push(index);
+				}
+			}
+		}
+
+		for row in y as usize..bottom as usize {
+			for column in x as usize..right as usize {
+				let index = column + row * w;
+				self.layers.set(index, field.data[index]);
+			}
+		}
+		for index in affected {
+			match exact_nearest_cell(field, &self.spacing, (index % w) as u32, (index / w) as u32, max_squared) {
+				Some((nearest_x, nearest_y)) => self.set_nearest_cell(index, nearest_x, nearest_y),
+				None => self.clear_nearest_cell(index),
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The coordinates of the (physically) nearest cell from the other layer within the maximum distance,
+/// searched ring by ring (a ring contains the cells with the same chebyshev distance) around the cell.
+/// The search ends, when no cell of the following rings can be nearer than the nearest cell found so far.
+fn exact_nearest_cell(field: &InputField, spacing: &Spacing, x: u32, y: u32, max_squared: f64) -> Option<(u32, u32)> {
+	let (w, h) = (field.width as i64, field.height as i64);
+	let (x, y) = (x as i64, y as i64);
+	let is_foreground = field.data[(x + y * w) as usize];
+	let last_ring = x.max(w - 1 - x).max(y).max(h - 1 - y);
+
+	// the cells of a ring are at least this far away (times the number of the ring)
+	let min_spacing = spacing.x.min(spacing.y);
+
+	// (squared distance, row offset, column offset) of the nearest cell
+	let mut nearest: Option<(f64, i64, i64)> = None;
+	for ring in 1..=last_ring {
+		let ring_distance = ring as f64 * min_spacing;
+		let limit_squared = match nearest {
+			Some((squared, _, _)) => squared,
+			None => max_squared,
+		};
+		if ring_distance * ring_distance > limit_squared {
+			break;
+		}
+		for dy in (-ring).max(-y)..=ring.min(h - 1 - y) {
+			// the top and the bottom row of the ring are complete, the other rows only have two cells
+			let step = if dy.abs() == ring { 1 } else { 2 * ring as usize };
+			for dx in (-ring..=ring).step_by(step) {
+				let nx = x + dx;
+				if nx < 0 || nx >= w || field.data[(nx + (y + dy) * w) as usize] == is_foreground {
+					continue;
+				}
+				let candidate = (spacing.distance_squared(dx.unsigned_abs() as u32, dy.unsigned_abs() as u32), dy, dx);
+				if nearest.is_none_or(|nearest| candidate < nearest) {
+					nearest = Some(candidate);
+				}
+			}
+		}
+	}
+
+	nearest
+		.filter(|(squared, _, _)| *squared <= max_squared)
+		.map(|(_, dy, dx)| ((x + dx) as u32, (y + dy) as u32))
+}
@@ -15,7 +15,7 @@ pub enum DistanceLayer {
 }
 
 /// Specification of all the different distance types that the library is able to calculate.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DistanceType {
 	/// The euclidean distance to the nearest cell.
 	/// The distance is a single, unsigned value.
@@ -1,4 +1,5 @@
 use crate::data::Cell;
+use crate::data::input::Spacing;
 use crate::distance::TwoDimensionalDistanceCalculation;
 use crate::utils::{i32_to_u8_clamped, i32_to_u16_clamped};
 
@@ -9,21 +10,29 @@ pub struct CartesianDistance;
 impl CartesianDistance {
 	// This is the default calculation for this distance type with maximum precision
 	pub fn calculate(cell : &Cell) -> (i32, i32) {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: i32 = nearest.x as i32 - cell.x as i32;
-			let dy: i32 = nearest.y as i32 - cell.y as i32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx as i32, dy as i32);
 			(dx, dy)
 		} else {
 			(0i32, 0i32)
 		}
 	}
+
+	/// The distance on both axes in physical units for the given spacing of the cells.
+	pub fn calculate_physical(cell: &Cell, spacing: &Spacing) -> (f64, f64) {
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx as f64 * spacing.x, dy as f64 * spacing.y);
+			(dx, dy)
+		} else {
+			(0f64, 0f64)
+		}
+	}
 }
 
 impl TwoDimensionalDistanceCalculation<u8> for CartesianDistance {
 	fn calculate_legacy(cell: &Cell) -> (u8, u8) {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: i32 = nearest.x as i32 - cell.x as i32;
-			let dy: i32 = nearest.y as i32 - cell.y as i32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx as i32, dy as i32);
 			(i32_to_u8_clamped(dx), i32_to_u8_clamped(dy))
 		} else {
 			(0u8, 0u8)
@@ -33,9 +42,8 @@ impl TwoDimensionalDistanceCalculation<u8> for CartesianDistance {
 
 impl TwoDimensionalDistanceCalculation<i32> for CartesianDistance {
 	fn calculate_legacy(cell: &Cell) -> (i32, i32) {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: i32 = nearest.x as i32 - cell.x as i32;
-			let dy: i32 = nearest.y as i32 - cell.y as i32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx as i32, dy as i32);
 			(dx, dy)
 		} else {
 			(0i32, 0i32)
@@ -45,9 +53,8 @@ impl TwoDimensionalDistanceCalculation<i32> for CartesianDistance {
 
 impl TwoDimensionalDistanceCalculation<u16> for CartesianDistance {
 	fn calculate_legacy(cell: &Cell) -> (u16, u16) {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx = nearest.x as i32 - cell.x as i32;
-			let dy = nearest.y as i32 - cell.y as i32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx as i32, dy as i32);
 			(i32_to_u16_clamped(dx), i32_to_u16_clamped(dy))
 		} else {
 			(0u16, 0u16)
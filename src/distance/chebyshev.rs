@@ -1,8 +1,9 @@
 use std::cmp::max;
 
 use crate::data::Cell;
+use crate::data::input::Spacing;
 use crate::distance::OneDimensionalDistanceCalculation;
-use crate::utils::u16_to_u8_clamped;
+use crate::utils::{u16_to_u8_clamped, u32_to_u16_clamped};
 
 /// The Chebyshev distance (also known as the chessboard distance) to the nearest cell.
 /// It is defined as the maximum of the horizontal distance and the vertical distance.
@@ -11,13 +12,22 @@ pub struct ChebyshevDistance;
 
 impl ChebyshevDistance {
 	// This is the default calculation for this distance type with maximum precision
-	pub fn calculate(cell : &Cell) -> u16 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: u16 = (nearest.x as i32 - cell.x as i32).abs() as u16;
-			let dy: u16 = (nearest.y as i32 - cell.y as i32).abs() as u16;
+	pub fn calculate(cell : &Cell) -> u32 {
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx.unsigned_abs() as u32, dy.unsigned_abs() as u32);
 			max(dx, dy)
 		} else {
-			0u16
+			0u32
+		}
+	}
+
+	/// The distance in physical units for the given spacing of the cells.
+	pub fn calculate_physical(cell: &Cell, spacing: &Spacing) -> f64 {
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx.unsigned_abs() as f64 * spacing.x, dy.unsigned_abs() as f64 * spacing.y);
+			dx.max(dy)
+		} else {
+			0f64
 		}
 	}
 }
@@ -30,12 +40,6 @@ impl OneDimensionalDistanceCalculation<u8> for ChebyshevDistance {
 
 impl OneDimensionalDistanceCalculation<u16> for ChebyshevDistance {
 	fn calculate_legacy(cell: &Cell) -> u16 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: u16 = (nearest.x as i32 - cell.x as i32).abs() as u16;
-			let dy: u16 = (nearest.y as i32 - cell.y as i32).abs() as u16;
-			max(dx, dy)
-		} else {
-			0u16
-		}
+		u32_to_u16_clamped(ChebyshevDistance::calculate(cell))
 	}
 }
\ No newline at end of file
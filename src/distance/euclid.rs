@@ -1,4 +1,5 @@
 use crate::data::Cell;
+use crate::data::input::Spacing;
 use crate::distance::OneDimensionalDistanceCalculation;
 use crate::utils::{f32_to_u8_clamped, f32_to_u16_clamped};
 
@@ -16,6 +17,11 @@ impl EuclideanDistance {
 			0f64
 		}
 	}
+
+	/// The distance in physical units for the given spacing of the cells.
+	pub fn calculate_physical(cell: &Cell, spacing: &Spacing) -> f64 {
+		EuclideanDistanceSquared::calculate_physical(cell, spacing).sqrt()
+	}
 }
 
 impl OneDimensionalDistanceCalculation<u8> for EuclideanDistance {
@@ -64,6 +70,15 @@ impl EuclideanDistanceSquared {
 			0
 		}
 	}
+
+	/// The squared distance in physical units for the given spacing of the cells.
+	pub fn calculate_physical(cell: &Cell, spacing: &Spacing) -> f64 {
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			spacing.distance_squared(dx.unsigned_abs() as u32, dy.unsigned_abs() as u32)
+		} else {
+			0f64
+		}
+	}
 }
 
 impl OneDimensionalDistanceCalculation<u8> for EuclideanDistanceSquared {
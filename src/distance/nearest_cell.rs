@@ -1,18 +1,20 @@
-use crate::data::Cell;
+use crate::data::{Cell, NO_CELL_INDEX};
 use crate::distance::{OneDimensionalDistanceCalculation, TwoDimensionalDistanceCalculation};
-use crate::utils::{u16_to_u8_clamped, u32_to_u16_clamped, u32_to_u8_clamped};
+use crate::utils::{i64_to_i32_clamped, u32_to_u16_clamped, u32_to_u8_clamped, u64_to_u32_clamped};
 
 /// The absolute index of the nearest cell in the 1-dimensional array of cells.
 /// This is a single, unsigned value.
+/// A virtual nearest cell beyond a constant border has the index `NO_CELL_INDEX`
+/// (clamped to the maximum value of the output type).
 pub struct NearestCellIndex;
 
 impl NearestCellIndex {
 	// This is the default calculation for this distance type with maximum precision
-	pub fn calculate_u32(cell: &Cell) -> u32 {
+	pub fn calculate_u64(cell: &Cell) -> u64 {
 		if let Some(nearest) = &cell.nearest_cell_position {
 			nearest.index
 		} else {
-			0u32
+			0u64
 		}
 	}
 }
@@ -31,11 +33,7 @@ impl OneDimensionalDistanceCalculation<u16> for NearestCellIndex {
 
 impl OneDimensionalDistanceCalculation<u32> for NearestCellIndex {
 	fn calculate_legacy(cell: &Cell) -> u32 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			nearest.index
-		} else {
-			0u32
-		}
+		u64_to_u32_clamped(NearestCellIndex::calculate_u64(cell))
 	}
 }
 
@@ -46,36 +44,33 @@ pub struct NearestCellPosition;
 
 impl NearestCellPosition {
 	// This is the default calculation for this distance type with maximum precision
-	pub fn calculate(cell : &Cell) -> (u16, u16) {
+	pub fn calculate(cell : &Cell) -> (u32, u32) {
 		if let Some(nearest) = &cell.nearest_cell_position {
 			(nearest.x, nearest.y)
 		} else {
-			(0u16, 0u16)
+			(0u32, 0u32)
 		}
 	}
 }
 
 impl TwoDimensionalDistanceCalculation<u8> for NearestCellPosition {
 	fn calculate_legacy(cell: &Cell) -> (u8, u8) {
-		let (x, y) = NearestCellPosition::calculate_legacy(&cell);
-		(u16_to_u8_clamped(x), u16_to_u8_clamped(y))
+		let (x, y) = NearestCellPosition::calculate(cell);
+		(u32_to_u8_clamped(x), u32_to_u8_clamped(y))
 	}
 }
 
 impl TwoDimensionalDistanceCalculation<u16> for NearestCellPosition {
 	fn calculate_legacy(cell: &Cell) -> (u16, u16) {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			(nearest.x, nearest.y)
-		} else {
-			(0u16, 0u16)
-		}
+		let (x, y) = NearestCellPosition::calculate(cell);
+		(u32_to_u16_clamped(x), u32_to_u16_clamped(y))
 	}
 }
 
 impl TwoDimensionalDistanceCalculation<u32> for NearestCellPosition {
 	fn calculate_legacy(cell: &Cell) -> (u32, u32) {
 		if let Some(nearest) = &cell.nearest_cell_position {
-			(nearest.x as u32, nearest.y as u32)
+			(nearest.x, nearest.y)
 		} else {
 			(0u32, 0u32)
 		}
@@ -83,16 +78,15 @@ impl TwoDimensionalDistanceCalculation<u32> for NearestCellPosition {
 }
 
 /// The relative distance of the nearest cell in the 1-dimensional array of cells.
-/// This is a single, signed value.
+/// This is a single, signed value. It is zero for a virtual nearest cell beyond a constant border.
 pub struct NearestCellIndexOffset;
 
 impl NearestCellIndexOffset {
 	// This is the default calculation for this distance type with maximum precision
-	pub fn calculate_i32(cell : &Cell) -> i32 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			nearest.index as i32 - cell.index as i32
-		} else {
-			0i32
+	pub fn calculate_i64(cell : &Cell) -> i64 {
+		match &cell.nearest_cell_position {
+			Some(nearest) if nearest.index != NO_CELL_INDEX => nearest.index as i64 - cell.index as i64,
+			_ => 0i64,
 		}
 	}
 }
@@ -120,10 +114,6 @@ impl OneDimensionalDistanceCalculation<u32> for NearestCellIndexOffset {
 
 impl OneDimensionalDistanceCalculation<i32> for NearestCellIndexOffset {
 	fn calculate_legacy(cell: &Cell) -> i32 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			nearest.index as i32 - cell.index as i32
-		} else {
-			0i32
-		}
+		i64_to_i32_clamped(NearestCellIndexOffset::calculate_i64(cell))
 	}
 }
\ No newline at end of file
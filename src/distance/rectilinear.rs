@@ -1,5 +1,6 @@
 use crate::distance::OneDimensionalDistanceCalculation;
 use crate::data::Cell;
+use crate::data::input::Spacing;
 use crate::utils::{u32_to_u16_clamped, u32_to_u8_clamped};
 
 /// The Rectilinear distance (also know as the Manhattan distance) to the nearest cell.
@@ -10,14 +11,23 @@ pub struct RectilinearDistance;
 impl RectilinearDistance {
 	// This is the default calculation for this distance type with maximum precision
 	pub fn calculate(cell : &Cell) -> u32 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: u32 = (nearest.x as i32 - cell.x as i32).abs() as u32;
-			let dy: u32 = (nearest.y as i32 - cell.y as i32).abs() as u32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx.unsigned_abs() as u32, dy.unsigned_abs() as u32);
 			dx + dy
 		} else {
 			0u32
 		}
 	}
+
+	/// The distance in physical units for the given spacing of the cells.
+	pub fn calculate_physical(cell: &Cell, spacing: &Spacing) -> f64 {
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx.unsigned_abs() as f64 * spacing.x, dy.unsigned_abs() as f64 * spacing.y);
+			dx + dy
+		} else {
+			0f64
+		}
+	}
 }
 
 impl OneDimensionalDistanceCalculation<u8> for RectilinearDistance {
@@ -34,9 +44,8 @@ impl OneDimensionalDistanceCalculation<u16> for RectilinearDistance {
 
 impl OneDimensionalDistanceCalculation<u32> for RectilinearDistance {
 	fn calculate_legacy(cell: &Cell) -> u32 {
-		if let Some(nearest) = &cell.nearest_cell_position {
-			let dx: u32 = (nearest.x as i32 - cell.x as i32).abs() as u32;
-			let dy: u32 = (nearest.y as i32 - cell.y as i32).abs() as u32;
+		if let Some((dx, dy)) = cell.nearest_vector() {
+			let (dx, dy) = (dx.unsigned_abs() as u32, dy.unsigned_abs() as u32);
 			dx + dy
 		} else {
 			0u32
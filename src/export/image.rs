@@ -3,8 +3,10 @@ use std::io::BufWriter;
 
 use png::{ColorType, Compression, Encoder, FilterType, BitDepth as PngBitDepth};
 
+use crate::data::input::Spacing;
 use crate::data::transformation::{TransformationData, TransformationResult, DataDescriptor};
 use crate::export::BitDepth;
+use crate::render::RgbaImage;
 use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDepth};
 
 // Todo: We have to add to the image exporter something like a color channel definition,
@@ -12,14 +14,24 @@ use crate::result::{DistanceTransformationResult, ChannelDataType, ChannelBitDep
 
 pub struct PngOutput {
 	file_path: String,
+	spacing: Option<Spacing>,
 }
 
 impl PngOutput {
 	pub fn new(file_path: &str) -> Self {
 		Self {
 			file_path: String::from(file_path),
+			spacing: None,
 		}
 	}
+
+	/// The spacing of the pixels in millimetres, it overrides the spacing of the written result.
+	/// The spacing is written as pHYs chunk (in pixels per metre), the chunk is omitted for
+	/// results with the default spacing (one on both axes), unless the spacing is set here.
+	pub fn spacing(mut self, spacing: Spacing) -> Self {
+		self.spacing = Some(spacing);
+		self
+	}
 }
 
 pub trait DistanceTransformationResultWriter {
@@ -33,10 +45,20 @@ impl DistanceTransformationResultWriter for PngOutput {
 		let height = trans_res.height;
 		let num_channels = trans_res.num_channels;
 		let bit_depth = trans_res.bit_depth;
-		self.output_image_file(data_buffer, width, height, num_channels, bit_depth);
+		self.output_image_file(data_buffer, width, height, num_channels, bit_depth, trans_res.spacing);
 	}
 }
 
+/// Writes rendered images (see the render module) as 8-bit RGBA files.
+pub trait RgbaImageWriter {
+	fn write_image(&self, image: &RgbaImage);
+}
+
+impl RgbaImageWriter for PngOutput {
+	fn write_image(&self, image: &RgbaImage) {
+		self.output_image_file(image.data.clone(), image.width, image.height, 4, ChannelBitDepth::Eight, Spacing::default());
+	}
+}
 
 pub trait ImageFileWriter {
 	fn write(&self, result_writer: &dyn TransformationResultWriter);
@@ -56,16 +78,18 @@ impl ImageFileWriter for PngOutput {
 							   descriptor.width,
 							   descriptor.height,
 							   descriptor.num_channels,
-							   depth);
+							   depth,
+							   descriptor.spacing);
 	}
 }
 
 impl PngOutput {
 	fn output_image_file(&self, image_data_buffer: Vec<u8>,
-						 width: u16,
-						 height: u16,
+						 width: u32,
+						 height: u32,
 						 num_channels: u8,
-						 bit_depth: ChannelBitDepth) {
+						 bit_depth: ChannelBitDepth,
+						 spacing: Spacing) {
 		let encoder = get_standard_encoder(&self.file_path,
 										   width,
 										   height,
@@ -75,6 +99,11 @@ impl PngOutput {
 		// TODO: wir haben auch configuration.channel_depth! Was nutzen wir hier?
 
 		let mut writer = encoder.write_header().unwrap();
+		match self.spacing {
+			Some(spacing) => writer.write_chunk(*b"pHYs", &physical_pixel_dimensions(&spacing)).unwrap(),
+			None if !spacing.is_unit() => writer.write_chunk(*b"pHYs", &physical_pixel_dimensions(&spacing)).unwrap(),
+			None => {}
+		}
 
 		// TODO: for two channels we need to add some padding bytes before !!!
 
@@ -134,8 +163,8 @@ impl PngOutput {
 
 
 fn get_standard_encoder(file_path: &str,
-						width: u16,
-						height: u16,
+						width: u32,
+						height: u32,
 						channel_depth: &ChannelBitDepth,
 						num_channels: u8) -> Encoder<BufWriter<File>> {
 	println!("{:?}", file_path);
@@ -190,7 +219,7 @@ fn get_standard_encoder(file_path: &str,
 	let file = File::create(file_path).unwrap();
 	let w = BufWriter::new(file);
 
-	let mut e = Encoder::new(w, width as u32, height as u32);
+	let mut e = Encoder::new(w, width, height);
 	match channels {
 		1 => e.set_color(ColorType::Grayscale),
 		2 => e.set_color(ColorType::GrayscaleAlpha),
@@ -209,6 +238,15 @@ fn get_standard_encoder(file_path: &str,
 	e
 }
 
+/// The data of a pHYs chunk: the pixels per metre on both axes (big endian) and the unit (one for metre).
+fn physical_pixel_dimensions(spacing: &Spacing) -> [u8; 9] {
+	let pixels_per_metre = |spacing: f64| (1000.0 / spacing).round() as u32;
+	let mut data = [1u8; 9];
+	data[0..4].copy_from_slice(&pixels_per_metre(spacing.x).to_be_bytes());
+	data[4..8].copy_from_slice(&pixels_per_metre(spacing.y).to_be_bytes());
+	data
+}
+
 // ******************************************************
 // new stuff - a lot of other code will be deprecated !!!
 // ******************************************************
@@ -220,14 +258,15 @@ pub trait TransformationResultWriter {
 
 impl dyn TransformationResultWriter {
 	pub fn get_descriptor<T>(&self, res: &TransformationResult<T>) -> DataDescriptor {
-		let (num_channels, width, height) = match res {
-			TransformationResult::OneDimensional(one) => (1, one.width, one.height),
-			TransformationResult::TwoDimensional(two) => (2, two.width, two.height),
-			TransformationResult::ThreeDimensional(three) => (3, three.width, three.height),
+		let (num_channels, width, height, spacing) = match res {
+			TransformationResult::OneDimensional(one) => (1, one.width, one.height, one.spacing),
+			TransformationResult::TwoDimensional(two) => (2, two.width, two.height, two.spacing),
+			TransformationResult::ThreeDimensional(three) => (3, three.width, three.height, three.spacing),
 		};
 		DataDescriptor {
 			width,
 			height,
+			spacing,
 			num_channels,
 			bit_depth: BitDepth::Eight,
 		}
@@ -1,17 +1,29 @@
+use std::cell::RefCell;
 use std::result::Result::Err;
 
 use crate::input::DistanceInput;
-use crate::processor::SourceProcessor;
+use crate::processor::{Processor, SourceProcessor};
 use crate::distance::{DistanceType, DistanceLayer};
 use crate::data::transformation::{DistanceTransformation, TransformOutputGenerator, TransformationResult};
 use crate::export::image::{ImageFileWriter};
 
+/// A warning of a successful generation (see `DistanceGenerator::warnings`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratorWarning {
+    /// The processor searches the nearest cells under the euclidean distance, but the distance type
+    /// is the Chebyshev or the rectilinear distance, so some distances can be too large
+    /// (see `SourceProcessor::minimizes`). The small errors of the euclidean distances
+    /// of the sweep processor are not reported.
+    NotMinimized(DistanceType),
+}
+
 pub struct DistanceGenerator {
     input: Option<Box<dyn DistanceInput>>,
     output: Option<Box<dyn ImageFileWriter>>,
     processor: Option<Box<dyn SourceProcessor>>,
     distance_type: DistanceType,
     distance_layer: DistanceLayer,
+    warnings: RefCell<Vec<GeneratorWarning>>,
 }
 
 impl DistanceGenerator {
@@ -22,6 +34,7 @@ impl DistanceGenerator {
             processor: None,
             distance_type: DistanceType::EuclideanDistance,
             distance_layer: DistanceLayer::Combined,
+            warnings: RefCell::new(Vec::new()),
         }
     }
 
@@ -35,6 +48,8 @@ impl DistanceGenerator {
         self
     }
 
+    /// The processor of the input field. Without a processor, the generator picks the processor
+    /// for the distance type and the spacing of the input (see `Processor::for_distance_type`).
     pub fn processor(mut self, processor: impl SourceProcessor + 'static) -> Self {
         self.processor = Some(Box::new(processor));
         self
@@ -50,32 +65,52 @@ impl DistanceGenerator {
         self
     }
 
+    /// The warnings of the last generation, e.g. if the distances of the output can be too large.
+    pub fn warnings(&self) -> Vec<GeneratorWarning> {
+        self.warnings.borrow().clone()
+    }
+
+    /// Generates the distance field and writes it to the output.
+    /// An error is returned, if there is no input or if the processor does not support the spacing of the input.
     pub fn generate(&self) -> Result<(), String> {
+        let mut warnings = self.warnings.borrow_mut();
+        warnings.clear();
         // input path is set?
         if let Some(input) = &self.input {
 
             // TODO: add matching here !!!
             let source = input.source_field().ok().unwrap(); //unwrap();
 
-            if let Some(processor) = &self.processor {
-                let df = processor.process(&source);
+            let default_processor;
+            let processor = match &self.processor {
+                Some(processor) => processor,
+                None => {
+                    default_processor = Processor::for_distance_type(self.distance_type, &source.spacing);
+                    &default_processor.processor
+                }
+            };
+            if !processor.supports_spacing(&source.spacing) {
+                return Err(format!("the processor does not support a spacing of {}x{}", source.spacing.x, source.spacing.y));
+            }
+            let is_non_euclidean = matches!(self.distance_type, DistanceType::ChebyshevDistance | DistanceType::RectilinearDistance);
+            if is_non_euclidean && !processor.minimizes(self.distance_type, &source.spacing) {
+                warnings.push(GeneratorWarning::NotMinimized(self.distance_type));
+            }
+            let df = processor.process(&source);
 
-                if let Some(output) = &self.output {
-                    // output.export(&df, &self.distance_type, &self.distance_layer);
+            if let Some(output) = &self.output {
+                // output.export(&df, &self.distance_type, &self.distance_layer);
 
-                    let mut dt: DistanceTransformation = DistanceTransformation::from(df);
-                    dt.filter(self.distance_layer);
-                    dt.distance_type(self.distance_type);
-                    dt.scale(0.9); // u8 -> 0 = orig, 1 = 2^1 = orig / 2, 2 = 2^2 = orig / 4, etc...
+                let mut dt: DistanceTransformation = DistanceTransformation::from(df);
+                dt.filter(self.distance_layer);
+                dt.distance_type(self.distance_type);
+                dt.scale(0.9); // u8 -> 0 = orig, 1 = 2^1 = orig / 2, 2 = 2^2 = orig / 4, etc...
 
-                    let res : TransformationResult<u8> = dt.transform();
-                    output.write(&res);
+                let res : TransformationResult<u8> = dt.transform();
+                output.write(&res);
 
-                } else {
-                    panic!("no export file specified");
-                }
             } else {
-                panic!("no processor specified");
+                panic!("no export file specified");
             }
 
             // we should tests and maybe micro-benchmark at least two known approaches here:
@@ -102,7 +137,36 @@ pub struct Configuration {}
 
 #[cfg(test)]
 mod tests {
-    use crate::generator::DistanceGenerator;
+    use crate::data::input::{BoolInputData, InputField, Spacing};
+    use crate::distance::DistanceType;
+    use crate::export::image::{ImageFileWriter, TransformationResultWriter};
+    use crate::generator::{DistanceGenerator, GeneratorWarning};
+    use crate::input::{DistanceInput, InputError};
+    use crate::processor::chamfer::{ChamferMask, ChamferProcessor};
+    use crate::processor::narrow_band::NarrowBandProcessor;
+    use crate::processor::sweep::EightSideSweepProcessor;
+
+    struct DotInput;
+
+    impl DistanceInput for DotInput {
+        fn source_field(&self) -> Result<InputField, InputError> {
+            Ok(InputField::from(BoolInputData::new(vec![false, false, true, false], 2, 2)))
+        }
+    }
+
+    struct ScaledDotInput;
+
+    impl DistanceInput for ScaledDotInput {
+        fn source_field(&self) -> Result<InputField, InputError> {
+            Ok(DotInput.source_field()?.spacing(Spacing::new(0.5, 0.5)))
+        }
+    }
+
+    struct NoOutput;
+
+    impl ImageFileWriter for NoOutput {
+        fn write(&self, _result_writer: &dyn TransformationResultWriter) {}
+    }
 
     #[test]
     fn no_input_path() {
@@ -110,6 +174,50 @@ mod tests {
         assert!(gen.generate().is_err(), "non existing input path should generate an error");
     }
 
+    #[test]
+    fn warns_about_processors_that_do_not_minimize_the_distance_type() {
+        let gen = DistanceGenerator::new()
+            .input(DotInput)
+            .output(NoOutput)
+            .processor(EightSideSweepProcessor {})
+            .distance_type(DistanceType::ChebyshevDistance);
+        assert_eq!(gen.generate(), Ok(()));
+        assert_eq!(gen.warnings(), vec![GeneratorWarning::NotMinimized(DistanceType::ChebyshevDistance)]);
+
+        let gen = DistanceGenerator::new()
+            .input(DotInput)
+            .output(NoOutput)
+            .processor(NarrowBandProcessor::new(4.0))
+            .distance_type(DistanceType::RectilinearDistance);
+        assert_eq!(gen.generate(), Ok(()));
+        assert_eq!(gen.warnings(), vec![GeneratorWarning::NotMinimized(DistanceType::RectilinearDistance)]);
+
+        let gen = DistanceGenerator::new()
+            .input(DotInput)
+            .output(NoOutput)
+            .distance_type(DistanceType::ChebyshevDistance);
+        assert_eq!(gen.generate(), Ok(()));
+        assert!(gen.warnings().is_empty());
+    }
+
+    #[test]
+    fn default_configuration_has_no_warnings() {
+        let gen = DistanceGenerator::new()
+            .input(DotInput)
+            .output(NoOutput);
+        assert_eq!(gen.generate(), Ok(()));
+        assert!(gen.warnings().is_empty());
+    }
+
+    #[test]
+    fn unsupported_spacing_is_an_error() {
+        let gen = DistanceGenerator::new()
+            .input(ScaledDotInput)
+            .output(NoOutput)
+            .processor(ChamferProcessor::new(ChamferMask::Chessboard));
+        assert!(gen.generate().is_err());
+    }
+
     /*
     #[tests]
     fn generates_output_file() {
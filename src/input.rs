@@ -10,6 +10,9 @@ use std::fmt;
 /// Image-based input types and functionality.
 pub mod image;
 
+/// Font-based input types and functionality (glyphs of TrueType and OpenType fonts).
+pub mod font;
+
 /// Type for a valid input for distance field generation.
 /// Implementors of this trait provide a SourceField that can be transformed to a distance field.
 pub trait DistanceInput {
@@ -0,0 +1,253 @@
+use std::fs;
+
+use bitvec::vec::BitVec;
+use ttf_parser::{Face, GlyphId, OutlineBuilder, Tag};
+use ttf_parser::gpos::{PairAdjustment, PositioningSubtable};
+
+use crate::data::input::InputField;
+use crate::input::InputError;
+use crate::input::image::FileInputError;
+
+/// Number of line segments used to approximate a curve of a glyph outline.
+const CURVE_SEGMENTS: u8 = 8;
+
+/// A TrueType or OpenType font that provides the glyphs of characters as input fields.
+pub struct FontInput {
+    data: Vec<u8>,
+}
+
+/// A rasterized glyph.
+/// All measures are given in pixels (for the size the glyph was rasterized with),
+/// the y axis points down and the pen position on the baseline is the origin.
+pub struct Glyph {
+    /// The rasterized outline (foreground is inside of the glyph).
+    /// This is `None` for glyphs without outline, e.g. the space character.
+    pub field: Option<InputField>,
+
+    /// The position of the top-left corner of the field.
+    pub left: f32,
+    pub top: f32,
+
+    /// The horizontal distance to the pen position of the next glyph.
+    pub advance: f32,
+}
+
+impl FontInput {
+    /// Opens the font file at the given path.
+    pub fn new(file_path: &str) -> Result<Self, InputError> {
+        let data = fs::read(file_path).map_err(|_| { FileInputError::InvalidFile })?;
+        FontInput::from_bytes(data)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, InputError> {
+        Face::parse(&data, 0).map_err(|err| InputError::InvalidInput { message: err.to_string() })?;
+        Ok(FontInput { data })
+    }
+
+    /// The distance from the baseline to the top of the highest glyphs (in pixels for the given size).
+    pub fn ascender(&self, size: f32) -> f32 {
+        let face = self.face();
+        face.ascender() as f32 * scale(&face, size)
+    }
+
+    /// The distance from the baseline to the bottom of the lowest glyphs (in pixels for the given size).
+    /// This is usually negative.
+    pub fn descender(&self, size: f32) -> f32 {
+        let face = self.face();
+        face.descender() as f32 * scale(&face, size)
+    }
+
+    /// Rasterizes the glyph of the given character, where the size is the height of the em square in pixels.
+    /// The field has the given number of background cells around the glyph outline.
+    /// Characters that are not part of the font are replaced by the default glyph of the font.
+    pub fn glyph(&self, character: char, size: f32, padding: u32) -> Glyph {
+        let face = self.face();
+        let scale = scale(&face, size);
+        let glyph_id = face.glyph_index(character).unwrap_or(GlyphId(0));
+        let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 * scale;
+
+        let mut outline = Outline::new(scale);
+        let bounds = match face.outline_glyph(glyph_id, &mut outline) {
+            Some(bounds) => bounds,
+            None => return Glyph { field: None, left: 0.0, top: 0.0, advance },
+        };
+
+        let padding = padding as i32;
+        let left = (bounds.x_min as f32 * scale).floor() as i32 - padding;
+        let right = (bounds.x_max as f32 * scale).ceil() as i32 + padding;
+        let top = (-bounds.y_max as f32 * scale).floor() as i32 - padding;
+        let bottom = (-bounds.y_min as f32 * scale).ceil() as i32 + padding;
+        let width = (right - left) as u32;
+        let height = (bottom - top) as u32;
+
+        let mut data = BitVec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            let inside = outline.inside_on_row(top as f32 + y as f32 + 0.5, left as f32, width);
+            inside.into_iter().for_each(|value| data.push(value));
+        }
+
+        Glyph {
+            field: Some(InputField::new(data, width, height)),
+            left: left as f32,
+            top: top as f32,
+            advance,
+        }
+    }
+
+    /// The kerning between two characters (in pixels for the given size).
+    /// The value is added to the advance of the left character.
+    /// The kerning is taken from the `kern` feature of the GPOS table, like text shapers do,
+    /// and only fonts without this feature use the legacy `kern` table.
+    pub fn kerning(&self, left: char, right: char, size: f32) -> f32 {
+        let face = self.face();
+        let (left, right) = match (face.glyph_index(left), face.glyph_index(right)) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return 0.0,
+        };
+        gpos_kerning(&face, left, right)
+            .or_else(|| kern_table_kerning(&face, left, right))
+            .map_or(0.0, |value| value as f32 * scale(&face, size))
+    }
+
+    fn face(&self) -> Face<'_> {
+        // the data has already been validated on construction
+        Face::parse(&self.data, 0).unwrap()
+    }
+}
+
+fn scale(face: &Face, size: f32) -> f32 {
+    size / face.units_per_em() as f32
+}
+
+/// The kerning of a glyph pair (in font units) from the pair adjustments of the `kern` feature
+/// of the GPOS table. This is `None`, if the font has no such feature.
+fn gpos_kerning(face: &Face, left: GlyphId, right: GlyphId) -> Option<i16> {
+    let gpos = face.tables().gpos?;
+    let mut lookups = gpos.features.into_iter()
+        .filter(|feature| feature.tag == Tag::from_bytes(b"kern"))
+        .flat_map(|feature| feature.lookup_indices)
+        .peekable();
+    lookups.peek()?;
+    let value = lookups
+        .filter_map(|index| gpos.lookups.get(index))
+        .flat_map(|lookup| lookup.subtables.into_iter::<PositioningSubtable>())
+        .find_map(|subtable| match subtable {
+            PositioningSubtable::Pair(adjustment) => pair_kerning(&adjustment, left, right),
+            _ => None,
+        });
+    Some(value.unwrap_or(0))
+}
+
+/// The horizontal advance adjustment of the left glyph, if the subtable applies to the pair.
+fn pair_kerning(adjustment: &PairAdjustment, left: GlyphId, right: GlyphId) -> Option<i16> {
+    let index = adjustment.coverage().get(left)?;
+    let (first, _) = match adjustment {
+        PairAdjustment::Format1 { sets, .. } => sets.get(index)?.get(right)?,
+        PairAdjustment::Format2 { classes, matrix, .. } => matrix.get((classes.0.get(left), classes.1.get(right)))?,
+    };
+    Some(first.x_advance)
+}
+
+/// The kerning of a glyph pair (in font units) from the legacy `kern` table.
+fn kern_table_kerning(face: &Face, left: GlyphId, right: GlyphId) -> Option<i16> {
+    face.tables().kern
+        .and_then(|kern| kern.subtables.into_iter()
+            .filter(|subtable| subtable.horizontal && !subtable.variable)
+            .find_map(|subtable| subtable.glyphs_kerning(left, right)))
+}
+
+/// The outline of a glyph as line segments in pixel coordinates (y axis pointing down).
+struct Outline {
+    scale: f32,
+    segments: Vec<((f32, f32), (f32, f32))>,
+    start: (f32, f32),
+    current: (f32, f32),
+}
+
+impl Outline {
+    fn new(scale: f32) -> Self {
+        Outline {
+            scale,
+            segments: Vec::new(),
+            start: (0.0, 0.0),
+            current: (0.0, 0.0),
+        }
+    }
+
+    fn to_pixels(&self, x: f32, y: f32) -> (f32, f32) {
+        (x * self.scale, -y * self.scale)
+    }
+
+    fn add_line(&mut self, to: (f32, f32)) {
+        self.segments.push((self.current, to));
+        self.current = to;
+    }
+
+    /// Evaluates the non-zero winding rule at the cell centers of a row.
+    fn inside_on_row(&self, y: f32, left: f32, width: u32) -> Vec<bool> {
+        let mut crossings: Vec<(f32, i32)> = self.segments.iter()
+            .filter(|(from, to)| (from.1 <= y) != (to.1 <= y))
+            .map(|(from, to)| {
+                let t = (y - from.1) / (to.1 - from.1);
+                let direction = if to.1 > from.1 { 1 } else { -1 };
+                (from.0 + (to.0 - from.0) * t, direction)
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut winding = 0;
+        let mut crossing = 0;
+        (0..width).map(|x| {
+            let center = left + x as f32 + 0.5;
+            while crossing < crossings.len() && crossings[crossing].0 <= center {
+                winding += crossings[crossing].1;
+                crossing += 1;
+            }
+            winding != 0
+        }).collect()
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.to_pixels(x, y);
+        self.current = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.to_pixels(x, y);
+        self.add_line(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let from = self.current;
+        let control = self.to_pixels(x1, y1);
+        let to = self.to_pixels(x, y);
+        for step in 1..=CURVE_SEGMENTS {
+            let t = step as f32 / CURVE_SEGMENTS as f32;
+            let mt = 1.0 - t;
+            self.add_line((mt * mt * from.0 + 2.0 * mt * t * control.0 + t * t * to.0,
+                           mt * mt * from.1 + 2.0 * mt * t * control.1 + t * t * to.1));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let from = self.current;
+        let first = self.to_pixels(x1, y1);
+        let second = self.to_pixels(x2, y2);
+        let to = self.to_pixels(x, y);
+        for step in 1..=CURVE_SEGMENTS {
+            let t = step as f32 / CURVE_SEGMENTS as f32;
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            self.add_line((a * from.0 + b * first.0 + c * second.0 + d * to.0,
+                           a * from.1 + b * first.1 + c * second.1 + d * to.1));
+        }
+    }
+
+    fn close(&mut self) {
+        if self.current != self.start {
+            self.add_line(self.start);
+        }
+    }
+}
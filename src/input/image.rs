@@ -1,23 +1,39 @@
+use std::collections::VecDeque;
 use std::fs::File;
+use std::io;
 
-use png::{Decoder, ColorType};
+use bitvec::vec::BitVec;
+use png::{Decoder, ColorType, BitDepth, PixelDimensions, Reader, Unit};
 use png::Transformations;
 
 use std::fmt;
 use crate::input::{DistanceInput, InputError};
-use crate::data::input::{InputField, ByteInputData};
+use crate::data::input::{InputField, ByteInputData, Spacing, is_supported_size};
+use crate::data::scalar::ScalarField;
+use crate::data::tile::{TileSource, check_region};
+use crate::render::RgbaImage;
 
 pub struct PngInput {
     file_path: String,
+    physical_spacing: bool,
 }
 
 impl PngInput {
     pub fn new(file_path: &str) -> Self {
         Self {
             file_path: String::from(file_path),
+            physical_spacing: false,
         }
     }
 
+    /// Read the spacing of the pixels from the pHYs chunk of the file (see `Spacing`), so distances are
+    /// measured in millimetres. This is disabled by default, since many image editors write a pHYs chunk
+    /// (e.g. with 72 dpi) without any physical meaning, and distances are measured in pixels then.
+    pub fn physical_spacing(mut self) -> Self {
+        self.physical_spacing = true;
+        self
+    }
+
     /// Opens a png file from the given path and converts it into a SourceField
     fn get_source_from_png_file_input(&self) -> Result<InputField, FileInputError> {
         let input_file = File::open(&self.file_path).map_err(|_| { FileInputError::InvalidFile })?;
@@ -38,6 +54,7 @@ impl PngInput {
         if info.color_type != ColorType::RGBA {
             return Err(FileInputError::InvalidImageFormat);
         }
+        check_size(info.width, info.height)?;
 
         println!("source png: {}", self.file_path);
         println!("size (w/h): {} * {}", info.width, info.height);
@@ -59,11 +76,194 @@ impl PngInput {
             *element = image_buffer[index * 4 + 3];
         }
 
-        // TODO: we must add some error handling here to handle the case that infos width/height is greater than u16::MAX !
-        let source = InputField::from(ByteInputData::new(output_buffer, 127, info.width as u16, info.height as u16));
+        let spacing = if self.physical_spacing {
+            spacing_from_pixel_dimensions(reader.info().pixel_dims)
+        } else {
+            Spacing::default()
+        };
+        let source = InputField::from(ByteInputData::new(output_buffer, 127, info.width, info.height)).spacing(spacing);
 
         Ok(source)
     }
+
+    /// Opens a png file from the given path and reads the values of its first channel
+    /// (e.g. the gray value of a grayscale image or the red value of a RGBA image).
+    /// This is used to read back distance fields that were exported as an image.
+    pub fn scalar_field(&self) -> Result<ScalarField, InputError> {
+        let field = self.get_scalar_field_from_png_file_input()?;
+        Ok(field)
+    }
+
+    /// Opens a png file (RGBA with 8 bits per channel) from the given path and reads all its channels.
+    pub fn rgba_image(&self) -> Result<RgbaImage, InputError> {
+        let image = self.get_rgba_image_from_png_file_input()?;
+        Ok(image)
+    }
+
+    fn get_rgba_image_from_png_file_input(&self) -> Result<RgbaImage, FileInputError> {
+        let input_file = File::open(&self.file_path).map_err(|_| { FileInputError::InvalidFile })?;
+
+        let mut d = Decoder::new(input_file);
+        d.set_transformations(Transformations::IDENTITY);
+
+        let (info, mut reader) = d.read_info().map_err(|_| { FileInputError::InvalidFileType })?;
+        if info.color_type != ColorType::RGBA || info.bit_depth != BitDepth::Eight {
+            return Err(FileInputError::InvalidImageFormat);
+        }
+        check_size(info.width, info.height)?;
+
+        let mut data = vec![0; info.buffer_size()];
+        reader.next_frame(&mut data).map_err(|_| { FileInputError::InvalidFileType })?;
+
+        Ok(RgbaImage {
+            width: info.width,
+            height: info.height,
+            data,
+        })
+    }
+
+    fn get_scalar_field_from_png_file_input(&self) -> Result<ScalarField, FileInputError> {
+        let input_file = File::open(&self.file_path).map_err(|_| { FileInputError::InvalidFile })?;
+
+        let mut d = Decoder::new(input_file);
+        d.set_transformations(Transformations::IDENTITY);
+
+        let (info, mut reader) = d.read_info().map_err(|_| { FileInputError::InvalidFileType })?;
+
+        let num_channels = match info.color_type {
+            ColorType::Grayscale => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::RGB => 3,
+            ColorType::RGBA => 4,
+            ColorType::Indexed => return Err(FileInputError::InvalidImageFormat),
+        };
+        let num_bytes = match info.bit_depth {
+            BitDepth::Eight => 1,
+            BitDepth::Sixteen => 2,
+            _ => return Err(FileInputError::InvalidImageFormat),
+        };
+        check_size(info.width, info.height)?;
+
+        let mut image_buffer = vec![0; info.buffer_size()];
+        reader.next_frame(&mut image_buffer).map_err(|_| { FileInputError::InvalidFileType })?;
+
+        // 16-bit values of png files are big endian
+        let data = image_buffer.chunks_exact(num_channels * num_bytes)
+            .map(|pixel| match num_bytes {
+                1 => pixel[0] as f32,
+                _ => u16::from_be_bytes([pixel[0], pixel[1]]) as f32,
+            })
+            .collect();
+
+        Ok(ScalarField::new(data, info.width, info.height))
+    }
+}
+
+/// A png file (RGBA with 8 bits per channel, not interlaced) as tile source of the `TiledProcessor`,
+/// e.g. for images that are too large to be decoded as a whole. Like for `PngInput`, cells with an
+/// alpha value of at least 127 are foreground cells.
+///
+/// The rows are decoded one by one while the regions are read, and only the rows from the top of the
+/// last region on are kept in memory. So the regions should be read from top to bottom (like the tiled
+/// processor does), the file is decoded again from its start for a region above these rows.
+pub struct PngTileSource {
+    file_path: String,
+    width: u32,
+    height: u32,
+    spacing: Spacing,
+    reader: Reader<File>,
+    rows: VecDeque<BitVec>,
+    first_row: u32,
+}
+
+impl PngTileSource {
+    pub fn open(file_path: &str) -> io::Result<Self> {
+        let reader = open_png_rows(file_path)?;
+        let (width, height) = (reader.info().width, reader.info().height);
+        Ok(PngTileSource {
+            file_path: String::from(file_path),
+            width,
+            height,
+            spacing: Spacing::default(),
+            reader,
+            rows: VecDeque::new(),
+            first_row: 0,
+        })
+    }
+
+    /// The physical size of the cells, the default is one on both axes.
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Read the spacing of the pixels from the pHYs chunk of the file (see `PngInput::physical_spacing`).
+    pub fn physical_spacing(mut self) -> Self {
+        self.spacing = spacing_from_pixel_dimensions(self.reader.info().pixel_dims);
+        self
+    }
+
+    /// Decodes the next row of the file into its foreground cells.
+    fn next_row(&mut self) -> io::Result<BitVec> {
+        match self.reader.next_row()? {
+            Some(row) => Ok(row.chunks_exact(4).map(|pixel| pixel[3] >= 127).collect()),
+            None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("{} has less than {} rows", self.file_path, self.height))),
+        }
+    }
+}
+
+impl TileSource for PngTileSource {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn spacing(&self) -> Spacing {
+        self.spacing
+    }
+
+    fn read_region(&mut self, x: u32, y: u32, width: u32, height: u32) -> io::Result<InputField> {
+        check_region(self.width, self.height, x, y, width, height);
+        if y < self.first_row {
+            self.reader = open_png_rows(&self.file_path)?;
+            self.rows.clear();
+            self.first_row = 0;
+        }
+        // the rows above the region are not needed anymore
+        while self.first_row < y {
+            if self.rows.pop_front().is_none() {
+                self.next_row()?;
+            }
+            self.first_row += 1;
+        }
+        while self.first_row as u64 + (self.rows.len() as u64) < y as u64 + height as u64 {
+            let row = self.next_row()?;
+            self.rows.push_back(row);
+        }
+
+        let mut data = BitVec::with_capacity(width as usize * height as usize);
+        for row in self.rows.iter().take(height as usize) {
+            for column in x as usize..x as usize + width as usize {
+                data.push(row[column]);
+            }
+        }
+        Ok(InputField::new(data, width, height).spacing(self.spacing))
+    }
+}
+
+/// Opens a png file for decoding row by row.
+fn open_png_rows(file_path: &str) -> io::Result<Reader<File>> {
+    let mut decoder = Decoder::new(File::open(file_path)?);
+    decoder.set_transformations(Transformations::IDENTITY);
+    let (info, reader) = decoder.read_info()?;
+    if info.color_type != ColorType::RGBA || info.bit_depth != BitDepth::Eight || reader.info().interlaced {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                  format!("{} is not a png file with 8 bit RGBA pixels without interlacing", file_path)));
+    }
+    Ok(reader)
 }
 
 // TODO: rename in SourceInputReader ! (or something similar)
@@ -88,6 +288,26 @@ pub enum FileInputError {
     InvalidFileType,
     // no PNG file
     InvalidImageFormat, // not RGBA or something
+    ImageTooLarge { width: u32, height: u32 }, // more pixels than a field can address (see MAX_CELLS)
+}
+
+/// Checks if the image can be converted into a field.
+fn check_size(width: u32, height: u32) -> Result<(), FileInputError> {
+    if is_supported_size(width, height) {
+        Ok(())
+    } else {
+        Err(FileInputError::ImageTooLarge { width, height })
+    }
+}
+
+/// The spacing of the pixels in millimetres from the pHYs chunk of a png file (in pixels per metre).
+/// Without the chunk, or if it only defines the aspect ratio of the pixels, the spacing is one on both axes.
+fn spacing_from_pixel_dimensions(pixel_dimensions: Option<PixelDimensions>) -> Spacing {
+    match pixel_dimensions {
+        Some(PixelDimensions { xppu, yppu, unit: Unit::Meter }) if xppu > 0 && yppu > 0 =>
+            Spacing::new(1000.0 / xppu as f64, 1000.0 / yppu as f64),
+        _ => Spacing::default(),
+    }
 }
 
 impl fmt::Display for FileInputError {
@@ -13,6 +13,12 @@ pub mod generator;
 
 pub mod processor;
 
+/// Operations that combine or modify distance fields
+pub mod operation;
+
+/// CPU-based rendering of distance fields
+pub mod render;
+
 /// Module for export related types and functionality
 pub mod export;
 
@@ -0,0 +1,17 @@
+//! Operations on distance fields.
+//! An operation takes one or more fields and derives a new field from them.
+
+/// Constructive solid geometry (union, intersection, subtraction) on signed fields.
+pub mod csg;
+
+/// Morphological operations (dilate, erode, open, close) based on distances.
+pub mod morphology;
+
+/// Contour (iso-line) extraction with marching squares.
+pub mod contour;
+
+/// Skeleton (medial axis) extraction based on the nearest cells.
+pub mod skeleton;
+
+/// Color dilation (alpha bleeding) of images based on the nearest cells.
+pub mod bleed;
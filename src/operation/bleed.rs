@@ -0,0 +1,55 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{CellLayer, NO_CELL_INDEX};
+use crate::data::input::InputField;
+use crate::distance::euclid::EuclideanDistance;
+use crate::distance::nearest_cell::NearestCellPosition;
+use crate::processor::SourceProcessor;
+use crate::render::RgbaImage;
+
+/// Fills the color channels of fully transparent pixels with the color of their nearest
+/// (partially) opaque pixel, while the alpha channel is kept.
+/// This prevents dark fringes around sprites when a texture is filtered or mip-mapped.
+pub struct AlphaBleed {
+	processor: Box<dyn SourceProcessor>,
+	max_distance: Option<f32>,
+}
+
+impl AlphaBleed {
+	pub fn new(processor: impl SourceProcessor + 'static) -> Self {
+		AlphaBleed {
+			processor: Box::new(processor),
+			max_distance: None,
+		}
+	}
+
+	/// Only fill the pixels within the given distance (in pixels) to an opaque pixel.
+	pub fn max_distance(mut self, distance: f32) -> Self {
+		self.max_distance = Some(distance);
+		self
+	}
+
+	pub fn apply(&self, image: &RgbaImage) -> RgbaImage {
+		let mut data = BitVec::with_capacity(image.width as usize * image.height as usize);
+		image.data.chunks_exact(4).for_each(|pixel| data.push(pixel[3] > 0));
+		let df = self.processor.process(&InputField::new(data, image.width, image.height));
+
+		let mut result = image.clone();
+		for cell in df.cells() {
+			// a virtual nearest cell beyond a constant border has no color
+			if cell.layer == CellLayer::Foreground || cell.nearest_cell_position.is_none_or(|nearest| nearest.index == NO_CELL_INDEX) {
+				continue;
+			}
+			if let Some(max_distance) = self.max_distance {
+				if EuclideanDistance::calculate(&cell) > max_distance as f64 {
+					continue;
+				}
+			}
+			let (x, y) = NearestCellPosition::calculate(&cell);
+			let [r, g, b, _] = image.get_pixel(x, y);
+			let alpha = image.get_pixel(cell.x, cell.y)[3];
+			result.set_pixel(cell.x, cell.y, [r, g, b, alpha]);
+		}
+		result
+	}
+}
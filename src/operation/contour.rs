@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::data::sampling::FieldSampler;
+
+/// A closed iso-line of a field.
+/// The points are given in cell units (integer coordinates hit the center of a cell),
+/// the last point is implicitly connected to the first one.
+#[derive(Debug, Clone)]
+pub struct Contour {
+	pub level: f32,
+	pub points: Vec<(f32, f32)>,
+}
+
+/// Extracts contours (iso-lines) from a field with the marching squares algorithm.
+/// The outside of the field is regarded to be above every level, so all contours are closed.
+pub struct ContourExtractor {
+	tolerance: Option<f32>,
+}
+
+/// An edge of the marching squares grid.
+/// Horizontal edges connect (x, y) and (x + 1, y), vertical edges (x, y) and (x, y + 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Edge {
+	Horizontal(i32, i32),
+	Vertical(i32, i32),
+}
+
+impl ContourExtractor {
+	pub fn new() -> Self {
+		ContourExtractor {
+			tolerance: None,
+		}
+	}
+
+	/// Simplify the extracted contours with the Douglas–Peucker algorithm.
+	/// Points that deviate less than the tolerance (in cell units) from a simplified line are removed.
+	pub fn simplify(mut self, tolerance: f32) -> Self {
+		self.tolerance = Some(tolerance);
+		self
+	}
+
+	/// Extract the contours of all given levels.
+	pub fn extract_levels(&self, field: &dyn FieldSampler, levels: &[f32]) -> Vec<Contour> {
+		levels.iter().flat_map(|level| self.extract(field, *level)).collect()
+	}
+
+	/// Extract all contours that separate values below the given level from values equal or above.
+	pub fn extract(&self, field: &dyn FieldSampler, level: f32) -> Vec<Contour> {
+		let value = |x: i32, y: i32| -> f32 {
+			if x < 0 || y < 0 || x >= field.width() as i32 || y >= field.height() as i32 {
+				level + 1.0
+			} else {
+				field.value_at(x as u32, y as u32)
+			}
+		};
+
+		// collect the segments of all squares (including the squares at the padded border)
+		let mut neighbours: HashMap<Edge, Vec<Edge>> = HashMap::new();
+		let mut edges: Vec<Edge> = Vec::new();
+		for y in -1..field.height() as i32 {
+			for x in -1..field.width() as i32 {
+				let corners = [value(x, y), value(x + 1, y), value(x + 1, y + 1), value(x, y + 1)];
+				let center_inside = corners.iter().sum::<f32>() / 4.0 < level;
+				for (from, to) in square_segments(x, y, &corners, level, center_inside) {
+					for (edge, other) in [(from, to), (to, from)].iter() {
+						let entry = neighbours.entry(*edge).or_insert_with(|| {
+							edges.push(*edge);
+							Vec::with_capacity(2)
+						});
+						entry.push(*other);
+					}
+				}
+			}
+		}
+
+		// link the segments to closed contours
+		let mut visited: HashSet<Edge> = HashSet::with_capacity(edges.len());
+		let mut contours = Vec::new();
+		for start in edges.iter() {
+			if visited.contains(start) {
+				continue;
+			}
+			let mut points = Vec::new();
+			let mut previous = *start;
+			let mut current = *start;
+			loop {
+				visited.insert(current);
+				points.push(edge_point(current, level, &value));
+				let next = neighbours[&current].iter()
+					.find(|edge| **edge != previous && !visited.contains(*edge))
+					.copied();
+				match next {
+					Some(edge) => {
+						previous = current;
+						current = edge;
+					}
+					None => break,
+				}
+			}
+			if let Some(tolerance) = self.tolerance {
+				points = simplify_closed(&points, tolerance);
+			}
+			contours.push(Contour { level, points });
+		}
+		contours
+	}
+}
+
+impl Default for ContourExtractor {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// The segments of a single square with the corners (top-left, top-right, bottom-right, bottom-left).
+fn square_segments(x: i32, y: i32, corners: &[f32; 4], level: f32, center_inside: bool) -> Vec<(Edge, Edge)> {
+	let top = Edge::Horizontal(x, y);
+	let right = Edge::Vertical(x + 1, y);
+	let bottom = Edge::Horizontal(x, y + 1);
+	let left = Edge::Vertical(x, y);
+
+	let case = corners.iter()
+		.enumerate()
+		.fold(0, |case, (bit, value)| if *value < level { case | 1 << bit } else { case });
+
+	match case {
+		1 | 14 => vec![(left, top)],
+		2 | 13 => vec![(top, right)],
+		3 | 12 => vec![(left, right)],
+		4 | 11 => vec![(right, bottom)],
+		6 | 9 => vec![(top, bottom)],
+		7 | 8 => vec![(bottom, left)],
+		// saddles are resolved by the value in the center of the square
+		5 => if center_inside {
+			vec![(top, right), (bottom, left)]
+		} else {
+			vec![(left, top), (right, bottom)]
+		},
+		10 => if center_inside {
+			vec![(left, top), (right, bottom)]
+		} else {
+			vec![(top, right), (bottom, left)]
+		},
+		_ => vec![],
+	}
+}
+
+/// The interpolated position of the level on the given edge.
+fn edge_point(edge: Edge, level: f32, value: &dyn Fn(i32, i32) -> f32) -> (f32, f32) {
+	let (x0, y0, x1, y1) = match edge {
+		Edge::Horizontal(x, y) => (x, y, x + 1, y),
+		Edge::Vertical(x, y) => (x, y, x, y + 1),
+	};
+	let v0 = value(x0, y0);
+	let v1 = value(x1, y1);
+	let t = if v1 != v0 { ((level - v0) / (v1 - v0)).clamp(0.0, 1.0) } else { 0.5 };
+	(x0 as f32 + (x1 - x0) as f32 * t, y0 as f32 + (y1 - y0) as f32 * t)
+}
+
+/// Douglas–Peucker simplification of a closed polyline.
+/// The polyline is split at the first point and the point farthest away from it.
+fn simplify_closed(points: &[(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
+	if points.len() < 4 {
+		return points.to_vec();
+	}
+	let first = points[0];
+	let (split, _) = points.iter()
+		.enumerate()
+		.map(|(index, point)| (index, squared_length(point.0 - first.0, point.1 - first.1)))
+		.fold((0, 0.0), |best, current| if current.1 > best.1 { current } else { best });
+
+	let mut closed = points.to_vec();
+	closed.push(first);
+
+	let mut result = simplify(&closed[..=split], tolerance);
+	result.pop();
+	result.extend(simplify(&closed[split..], tolerance));
+	result.pop();
+	result
+}
+
+/// Douglas–Peucker simplification of an open polyline (the end points are kept).
+fn simplify(points: &[(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
+	if points.len() < 3 {
+		return points.to_vec();
+	}
+	let first = points[0];
+	let last = points[points.len() - 1];
+	let (index, distance) = points[1..points.len() - 1].iter()
+		.enumerate()
+		.map(|(index, point)| (index + 1, distance_to_line(*point, first, last)))
+		.fold((0, 0.0), |best, current| if current.1 > best.1 { current } else { best });
+
+	if distance > tolerance {
+		let mut result = simplify(&points[..=index], tolerance);
+		result.pop();
+		result.extend(simplify(&points[index..], tolerance));
+		result
+	} else {
+		vec![first, last]
+	}
+}
+
+fn distance_to_line(point: (f32, f32), start: (f32, f32), end: (f32, f32)) -> f32 {
+	let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+	let length_squared = squared_length(dx, dy);
+	if length_squared == 0.0 {
+		return squared_length(point.0 - start.0, point.1 - start.1).sqrt();
+	}
+	((point.0 - start.0) * dy - (point.1 - start.1) * dx).abs() / length_squared.sqrt()
+}
+
+fn squared_length(dx: f32, dy: f32) -> f32 {
+	dx * dx + dy * dy
+}
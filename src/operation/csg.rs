@@ -0,0 +1,71 @@
+use crate::data::scalar::ScalarField;
+use crate::data::input::InputField;
+use crate::processor::SourceProcessor;
+
+/// CSG operations on signed fields.
+/// The operands must have the same dimensions and contain signed distances
+/// (negative inside, positive outside) like the fields created from a distance field.
+impl ScalarField {
+	/// The union of both shapes (minimum of the distances).
+	pub fn union(&self, other: &ScalarField) -> ScalarField {
+		self.combine(other, |a, b| a.min(b))
+	}
+
+	/// The intersection of both shapes (maximum of the distances).
+	pub fn intersection(&self, other: &ScalarField) -> ScalarField {
+		self.combine(other, |a, b| a.max(b))
+	}
+
+	/// This shape with the other shape cut out of it.
+	pub fn subtraction(&self, other: &ScalarField) -> ScalarField {
+		self.combine(other, |a, b| a.max(-b))
+	}
+
+	/// The union of both shapes with a rounded blend of the given radius.
+	pub fn smooth_union(&self, other: &ScalarField, radius: f32) -> ScalarField {
+		self.combine(other, |a, b| smooth_min(a, b, radius))
+	}
+
+	/// The intersection of both shapes with a rounded blend of the given radius.
+	pub fn smooth_intersection(&self, other: &ScalarField, radius: f32) -> ScalarField {
+		self.combine(other, |a, b| -smooth_min(-a, -b, radius))
+	}
+
+	/// This shape with the other shape cut out of it, with a rounded blend of the given radius.
+	pub fn smooth_subtraction(&self, other: &ScalarField, radius: f32) -> ScalarField {
+		self.combine(other, |a, b| -smooth_min(-a, b, radius))
+	}
+
+	/// Turns the field into a valid signed distance field again.
+	/// After combining fields the values are only bounds of the real distances,
+	/// so the shape (all negative cells) is processed again by the given processor
+	/// (with the spacing of the field, so the distances stay in the same units).
+	pub fn reinitialize(&self, processor: &dyn SourceProcessor) -> ScalarField {
+		let df = processor.process(&InputField::from(self));
+		ScalarField::from(&df)
+	}
+
+	fn combine(&self, other: &ScalarField, function: impl Fn(f32, f32) -> f32) -> ScalarField {
+		if self.width != other.width || self.height != other.height {
+			panic!("fields must have the same dimensions ({}x{} vs. {}x{})", self.width, self.height, other.width, other.height);
+		}
+		if self.spacing != other.spacing {
+			panic!("fields must have the same spacing ({}x{} vs. {}x{})", self.spacing.x, self.spacing.y, other.spacing.x, other.spacing.y);
+		}
+		let data = self.data.iter()
+			.zip(other.data.iter())
+			.map(|(a, b)| function(*a, *b))
+			.collect();
+		ScalarField::new(data, self.width, self.height).spacing(self.spacing)
+	}
+}
+
+/// Polynomial smooth minimum of two values.
+/// The result is equal to the minimum, if the values differ by more than the radius.
+fn smooth_min(a: f32, b: f32, radius: f32) -> f32 {
+	if radius <= 0.0 {
+		return a.min(b);
+	}
+	let h = (0.5 + 0.5 * (b - a) / radius).clamp(0.0, 1.0);
+	b + (a - b) * h - radius * h * (1.0 - h)
+}
@@ -0,0 +1,116 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{Cell, CellLayer};
+use crate::data::input::{InputField, Spacing};
+use crate::distance::DistanceType;
+use crate::distance::euclid::EuclideanDistance;
+use crate::distance::chebyshev::ChebyshevDistance;
+use crate::distance::rectilinear::RectilinearDistance;
+use crate::processor::narrow_band::NarrowBandProcessor;
+use crate::processor::{Processor, SourceProcessor};
+
+/// Morphological operations (offset, dilate, erode, open, close) based on distance fields.
+/// The shape of the structuring element is given by the metric:
+/// a disk for the euclidean distance, a square for the Chebyshev distance and
+/// a diamond for the rectilinear distance.
+/// The radius is measured in physical units (see `Spacing`).
+pub struct Morphology {
+	processor: Option<Box<dyn SourceProcessor>>,
+	metric: DistanceType,
+}
+
+impl Morphology {
+	/// Create the operations for the given processor and metric.
+	/// Only `EuclideanDistance`, `ChebyshevDistance` and `RectilinearDistance` are valid metrics.
+	///
+	/// The metric is applied to the nearest cells of the processor. If the processor does not minimize
+	/// the metric (see `SourceProcessor::minimizes`), cells within the radius can be missed: e.g. the sweep
+	/// finds the euclidean nearest cell (4, 0) of the cell (0, 0) and not the cell (3, 3), which is nearer
+	/// under the Chebyshev distance. Use `exact` to avoid this.
+	pub fn new(processor: impl SourceProcessor + 'static, metric: DistanceType) -> Self {
+		// fail early for distance types that are no metric
+		metric_function(metric);
+		Morphology {
+			processor: Some(Box::new(processor)),
+			metric,
+		}
+	}
+
+	/// Create the operations for the given metric with a processor, that minimizes the metric:
+	/// a narrow band of the radius for the euclidean distance and the processor of
+	/// `Processor::for_distance_type` otherwise. The chamfer masks of the Chebyshev and the rectilinear
+	/// distance measure distances in cells, so fields with another spacing fall back to the sweep.
+	pub fn exact(metric: DistanceType) -> Self {
+		metric_function(metric);
+		Morphology {
+			processor: None,
+			metric,
+		}
+	}
+
+	/// Grow (positive radius) or shrink (negative radius) the foreground of the field.
+	pub fn offset(&self, field: &InputField, radius: f32) -> InputField {
+		if radius > 0.0 {
+			self.dilate(field, radius)
+		} else if radius < 0.0 {
+			self.erode(field, -radius)
+		} else {
+			InputField::new(field.data.clone(), field.width, field.height).spacing(field.spacing)
+		}
+	}
+
+	/// Grow the foreground by the given radius.
+	/// All background cells within the radius around a foreground cell become foreground.
+	pub fn dilate(&self, field: &InputField, radius: f32) -> InputField {
+		self.flip_cells_within(field, CellLayer::Background, radius)
+	}
+
+	/// Shrink the foreground by the given radius.
+	/// All foreground cells within the radius around a background cell become background.
+	pub fn erode(&self, field: &InputField, radius: f32) -> InputField {
+		self.flip_cells_within(field, CellLayer::Foreground, radius)
+	}
+
+	/// Erosion followed by a dilation. This removes foreground details smaller than the radius.
+	pub fn open(&self, field: &InputField, radius: f32) -> InputField {
+		self.dilate(&self.erode(field, radius), radius)
+	}
+
+	/// Dilation followed by an erosion. This closes background gaps smaller than the radius.
+	pub fn close(&self, field: &InputField, radius: f32) -> InputField {
+		self.erode(&self.dilate(field, radius), radius)
+	}
+
+	fn flip_cells_within(&self, field: &InputField, layer: CellLayer, radius: f32) -> InputField {
+		let distance = metric_function(self.metric);
+		let df = match &self.processor {
+			Some(processor) => processor.process(field),
+			None => self.exact_processor(&field.spacing, radius).process(field),
+		};
+		let mut data = BitVec::with_capacity(df.len());
+		df.cells().for_each(|cell| {
+			let is_foreground = cell.layer == CellLayer::Foreground;
+			let flip = cell.layer == layer
+				&& cell.nearest_cell_position.is_some()
+				&& distance(&cell, &df.spacing) <= radius as f64;
+			data.push(is_foreground != flip);
+		});
+		InputField::new(data, df.width, df.height).spacing(df.spacing)
+	}
+
+	fn exact_processor(&self, spacing: &Spacing, radius: f32) -> Box<dyn SourceProcessor> {
+		match self.metric {
+			DistanceType::EuclideanDistance => Box::new(NarrowBandProcessor::new(radius.max(1.0))),
+			metric => Processor::for_distance_type(metric, spacing).processor,
+		}
+	}
+}
+
+fn metric_function(metric: DistanceType) -> fn(&Cell, &Spacing) -> f64 {
+	match metric {
+		DistanceType::EuclideanDistance => EuclideanDistance::calculate_physical,
+		DistanceType::ChebyshevDistance => ChebyshevDistance::calculate_physical,
+		DistanceType::RectilinearDistance => RectilinearDistance::calculate_physical,
+		_ => panic!("distance type {} is not supported as a metric", metric.human_readable_name()),
+	}
+}
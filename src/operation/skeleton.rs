@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use crate::data::{Cell, CellLayer, DistanceField};
+use crate::distance::DistanceLayer;
+use crate::distance::euclid::EuclideanDistance;
+
+/// A single point of a skeleton with its local radius
+/// (the distance to the nearest cell of the opposite layer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkeletonPoint {
+	pub x: u32,
+	pub y: u32,
+	pub radius: f32,
+}
+
+/// The medial axis of the shapes in a distance field.
+pub struct Skeleton {
+	pub width: u32,
+	pub height: u32,
+	pub points: Vec<SkeletonPoint>,
+}
+
+/// Extracts the skeleton (medial axis) from the feature transform of a distance field.
+/// A cell belongs to the skeleton, if the nearest cells of the cell and one of its neighbours
+/// diverge, i.e. they are further apart than the distance threshold or the directions
+/// to them differ by more than the angle threshold.
+pub struct SkeletonExtractor {
+	layer: DistanceLayer,
+	distance_threshold: f32,
+	angle_threshold: f32,
+	min_branch_length: usize,
+}
+
+impl SkeletonExtractor {
+	pub fn new() -> Self {
+		SkeletonExtractor {
+			layer: DistanceLayer::Foreground,
+			distance_threshold: 2.0,
+			angle_threshold: 60f32.to_radians(),
+			min_branch_length: 0,
+		}
+	}
+
+	/// The layer whose skeleton will be extracted.
+	/// This is the foreground by default, `Combined` extracts the skeletons of both layers.
+	pub fn layer(mut self, layer: DistanceLayer) -> Self {
+		self.layer = layer;
+		self
+	}
+
+	/// The minimum distance (in cells) between the nearest cells of neighbours.
+	pub fn distance_threshold(mut self, distance: f32) -> Self {
+		self.distance_threshold = distance;
+		self
+	}
+
+	/// The minimum angle (in degrees) between the directions to the nearest cells of neighbours.
+	pub fn angle_threshold(mut self, degrees: f32) -> Self {
+		self.angle_threshold = degrees.to_radians();
+		self
+	}
+
+	/// Remove spurious branches that are shorter than the given number of cells.
+	pub fn prune(mut self, min_branch_length: usize) -> Self {
+		self.min_branch_length = min_branch_length;
+		self
+	}
+
+	pub fn extract(&self, df: &DistanceField) -> Skeleton {
+		let w = df.width as usize;
+		let h = df.height as usize;
+
+		let mut is_skeleton = vec![false; w * h];
+		for y in 0..h {
+			for x in 0..w {
+				let index = x + y * w;
+				let cell = df.cell(index);
+				if !self.is_selected(&cell) {
+					continue;
+				}
+				// compare with the right, bottom-left, bottom and bottom-right neighbour
+				let neighbours = [(1, 0), (-1, 1), (0, 1), (1, 1)];
+				for (dx, dy) in neighbours.iter() {
+					let nx = x as i32 + dx;
+					let ny = y as i32 + dy;
+					if nx < 0 || nx >= w as i32 || ny >= h as i32 {
+						continue;
+					}
+					let neighbour_index = nx as usize + ny as usize * w;
+					let neighbour = df.cell(neighbour_index);
+					if neighbour.layer != cell.layer || !self.diverge(&cell, &neighbour) {
+						continue;
+					}
+					// the cell with the larger distance is the one nearer to the medial axis
+					if EuclideanDistance::calculate(&neighbour) > EuclideanDistance::calculate(&cell) {
+						is_skeleton[neighbour_index] = true;
+					} else {
+						is_skeleton[index] = true;
+					}
+				}
+			}
+		}
+
+		if self.min_branch_length > 1 {
+			prune_branches(&mut is_skeleton, w, h, self.min_branch_length);
+		}
+
+		let points = is_skeleton.iter()
+			.enumerate()
+			.filter(|(_, is_skeleton)| **is_skeleton)
+			.map(|(index, _)| SkeletonPoint {
+				x: (index % w) as u32,
+				y: (index / w) as u32,
+				radius: EuclideanDistance::calculate(&df.cell(index)) as f32,
+			})
+			.collect();
+
+		Skeleton {
+			width: df.width,
+			height: df.height,
+			points,
+		}
+	}
+
+	fn is_selected(&self, cell: &Cell) -> bool {
+		cell.nearest_cell_position.is_some() && match self.layer {
+			DistanceLayer::Foreground => cell.layer == CellLayer::Foreground,
+			DistanceLayer::Background => cell.layer == CellLayer::Background,
+			DistanceLayer::Combined => true,
+		}
+	}
+
+	/// The vectors to the nearest cells include the border offsets (see `Cell::nearest_vector`),
+	/// so nearest cells beyond a periodic or constant border are compared at their actual position.
+	fn diverge(&self, cell: &Cell, neighbour: &Cell) -> bool {
+		let ((ax, ay), (bx, by)) = match (cell.nearest_vector(), neighbour.nearest_vector()) {
+			(Some(first), Some(second)) => (first, second),
+			_ => return false,
+		};
+
+		// the vector from the nearest cell of the cell to the nearest cell of the neighbour
+		let dx = neighbour.x as i64 - cell.x as i64 + bx - ax;
+		let dy = neighbour.y as i64 - cell.y as i64 + by - ay;
+		let distance_squared = (dx * dx + dy * dy) as f32;
+		if distance_squared > self.distance_threshold * self.distance_threshold {
+			return true;
+		}
+
+		let (ax, ay) = (ax as f32, ay as f32);
+		let (bx, by) = (bx as f32, by as f32);
+		let lengths = (ax * ax + ay * ay).sqrt() * (bx * bx + by * by).sqrt();
+		if lengths == 0.0 {
+			return false;
+		}
+		let cos = ((ax * bx + ay * by) / lengths).clamp(-1.0, 1.0);
+		cos.acos() > self.angle_threshold
+	}
+}
+
+impl Default for SkeletonExtractor {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Removes all branches (from an end point to a junction or another end point) that are shorter than the minimum length.
+fn prune_branches(is_skeleton: &mut [bool], w: usize, h: usize, min_branch_length: usize) {
+	let neighbours = |index: usize, is_skeleton: &[bool]| -> Vec<usize> {
+		let (x, y) = ((index % w) as i32, (index / w) as i32);
+		let mut result = Vec::with_capacity(8);
+		for dy in -1..=1 {
+			for dx in -1..=1 {
+				let (nx, ny) = (x + dx, y + dy);
+				if (dx != 0 || dy != 0) && nx >= 0 && ny >= 0 && nx < w as i32 && ny < h as i32 {
+					let neighbour = nx as usize + ny as usize * w;
+					if is_skeleton[neighbour] {
+						result.push(neighbour);
+					}
+				}
+			}
+		}
+		result
+	};
+
+	let end_points: Vec<usize> = (0..is_skeleton.len())
+		.filter(|index| is_skeleton[*index] && neighbours(*index, is_skeleton).len() <= 1)
+		.collect();
+
+	for end_point in end_points {
+		let mut branch = vec![end_point];
+		let mut visited: HashSet<usize> = branch.iter().copied().collect();
+		let mut current = end_point;
+		while branch.len() < min_branch_length {
+			let next: Vec<usize> = neighbours(current, is_skeleton).into_iter()
+				.filter(|index| !visited.contains(index))
+				.collect();
+			// stop at the other end of the branch or at a junction
+			if next.len() != 1 || neighbours(next[0], is_skeleton).len() > 2 {
+				break;
+			}
+			current = next[0];
+			visited.insert(current);
+			branch.push(current);
+		}
+		if branch.len() < min_branch_length {
+			branch.iter().for_each(|index| is_skeleton[*index] = false);
+		}
+	}
+}
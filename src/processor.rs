@@ -1,20 +1,67 @@
 use crate::data::DistanceField;
-use crate::data::input::InputField;
+use crate::data::input::{InputField, Spacing};
+use crate::distance::DistanceType;
+use crate::processor::chamfer::{ChamferMask, ChamferProcessor};
 use crate::processor::sweep::EightSideSweepProcessor;
 
 pub mod sweep;
+pub mod label;
+pub mod boundary;
+pub mod narrow_band;
+pub mod tiled;
+pub mod geodesic;
+pub mod fast_marching;
+pub mod chamfer;
+pub mod jump_flooding;
+mod queue;
 
 /// A SourceProcessor takes a SourceField and turns it into a DistanceField
 /// (based on some internal algorithm to calculate the distances).
 pub trait SourceProcessor {
     /// Generate a distance field for the source field.
     fn process(&self, field: &InputField) -> DistanceField;
+
+    /// Whether the nearest cells of the generated distance fields are guaranteed to be the nearest
+    /// cells under the given distance type for an input field with the given spacing.
+    /// Otherwise some distances can be too large, e.g. the euclidean nearest cells of the sweep
+    /// are not exact, and they are not necessarily the nearest cells under the Chebyshev or the
+    /// rectilinear distance at all. Processors have to opt in, the default is false.
+    fn minimizes(&self, _distance_type: DistanceType, _spacing: &Spacing) -> bool {
+        false
+    }
+
+    /// The maximum distance (in physical units) of the nearest cells of the generated distance fields,
+    /// cells farther away from the boundary have no nearest cell. The default is no maximum.
+    fn max_distance(&self) -> Option<f64> {
+        None
+    }
+
+    /// Whether the processor can process input fields with the given spacing.
+    /// The default is true, e.g. the chamfer processors only support the default spacing.
+    fn supports_spacing(&self, _spacing: &Spacing) -> bool {
+        true
+    }
 }
 
 pub struct Processor {
     pub processor : Box<dyn SourceProcessor>,
 }
 
+impl Processor {
+    /// The processor for the given distance type and the spacing of the input field: the chamfer
+    /// processor with the exact mask for the Chebyshev and the rectilinear distance for the default
+    /// spacing, the sweep processor otherwise. The masks measure distances in cells, so they are not
+    /// used for other spacings. Only the chamfer processor minimizes its distance type, see `SourceProcessor::minimizes`.
+    pub fn for_distance_type(distance_type: DistanceType, spacing: &Spacing) -> Self {
+        match ChamferMask::for_distance_type(distance_type) {
+            Some(mask) if spacing.is_unit() => Self {
+                processor: Box::new(ChamferProcessor::new(mask)),
+            },
+            _ => Self::from(EightSideSweepProcessor {}),
+        }
+    }
+}
+
 impl From<EightSideSweepProcessor> for Processor {
     fn from(proc: EightSideSweepProcessor) -> Self {
         Self {
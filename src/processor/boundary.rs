@@ -0,0 +1,177 @@
+use bitvec::vec::BitVec;
+
+use crate::data::{Border, DistanceField};
+use crate::data::input::{InputField, Spacing};
+use crate::distance::DistanceType;
+use crate::processor::SourceProcessor;
+
+/// The behaviour of a field beyond one of its borders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Boundary {
+	/// There is nothing beyond the border, only the cells of the field are regarded.
+	Ignore,
+
+	/// The field wraps around, i.e. the cells at the opposite border are neighbours.
+	/// This is needed for seamlessly tiling patterns.
+	Periodic,
+
+	/// Everything beyond the border is background.
+	/// Foreground cells at the border get a distance of one.
+	Background,
+
+	/// Everything beyond the border is foreground.
+	/// Background cells at the border get a distance of one.
+	Foreground,
+}
+
+/// Decorator that adds boundary handling to any other processor.
+/// The input field is extended beyond its borders according to the boundary mode of each axis,
+/// processed by the inner processor and cropped to its original size again.
+/// This way the boundary modes behave the same for every processor.
+/// In the corners of the extended field a constant boundary mode (background or foreground)
+/// takes precedence over a periodic one, and the horizontal mode over the vertical one.
+///
+/// The cells of the result have the coordinates of the original field. A nearest cell across a
+/// periodic border is wrapped around to its position in the field, a nearest cell beyond a constant
+/// border is clamped to the adjacent border cell. The offset to the actual position of the nearest
+/// cell is kept as border offset (see `Cell::border_offset`), so the vectors and distances to
+/// nearest cells across a border stay valid.
+/// A nearest cell beyond a constant border is a virtual cell, that has no index in the field:
+/// its index is `NO_CELL_INDEX`.
+pub struct BoundaryProcessor {
+	processor: Box<dyn SourceProcessor>,
+	horizontal: Boundary,
+	vertical: Boundary,
+}
+
+impl BoundaryProcessor {
+	pub fn new(processor: impl SourceProcessor + 'static) -> Self {
+		BoundaryProcessor {
+			processor: Box::new(processor),
+			horizontal: Boundary::Ignore,
+			vertical: Boundary::Ignore,
+		}
+	}
+
+	/// The boundary mode of the left and right border.
+	pub fn horizontal(mut self, boundary: Boundary) -> Self {
+		self.horizontal = boundary;
+		self
+	}
+
+	/// The boundary mode of the top and bottom border.
+	pub fn vertical(mut self, boundary: Boundary) -> Self {
+		self.vertical = boundary;
+		self
+	}
+
+	/// The boundary mode of all borders.
+	pub fn boundary(self, boundary: Boundary) -> Self {
+		self.horizontal(boundary).vertical(boundary)
+	}
+}
+
+impl SourceProcessor for BoundaryProcessor {
+	fn process(&self, field: &InputField) -> DistanceField {
+		let margin_x = margin(self.horizontal, field.width);
+		let margin_y = margin(self.vertical, field.height);
+		if margin_x == 0 && margin_y == 0 {
+			return self.processor.process(field);
+		}
+
+		let width = extended_size(field.width, margin_x);
+		let height = extended_size(field.height, margin_y);
+		let mut data = BitVec::with_capacity(width as usize * height as usize);
+		for y in 0..height as i64 {
+			for x in 0..width as i64 {
+				let value = match (source_coordinate(self.horizontal, x - margin_x as i64, field.width),
+								   source_coordinate(self.vertical, y - margin_y as i64, field.height)) {
+					(Ok(source_x), Ok(source_y)) => field.data[source_x as usize + source_y as usize * field.width as usize],
+					(Err(value), _) | (_, Err(value)) => value,
+				};
+				data.push(value);
+			}
+		}
+		let extended = self.processor.process(&InputField::new(data, width, height).spacing(field.spacing));
+
+		// crop the result, nearest cells beyond the borders are moved into the field
+		let mut layers = BitVec::with_capacity(field.width as usize * field.height as usize);
+		for y in margin_y..margin_y + field.height {
+			for x in margin_x..margin_x + field.width {
+				layers.push(extended.layers[x as usize + y as usize * width as usize]);
+			}
+		}
+		let mut df = DistanceField::from_layers(layers, field.width, field.height);
+		df.spacing = field.spacing;
+		df.border = Some(Border::new(df.len()));
+		for y in 0..field.height {
+			for x in 0..field.width {
+				let extended_index = (x + margin_x) as usize + (y + margin_y) as usize * width as usize;
+				if let Some(nearest) = extended.nearest_cell_position(extended_index) {
+					let index = x as usize + y as usize * field.width as usize;
+					let (nearest_x, offset_x) = field_coordinate(self.horizontal, nearest.x as i64 - margin_x as i64, field.width);
+					let (nearest_y, offset_y) = field_coordinate(self.vertical, nearest.y as i64 - margin_y as i64, field.height);
+					df.set_nearest_cell(index, nearest_x, nearest_y);
+					let is_virtual = (offset_x != 0 && self.horizontal != Boundary::Periodic)
+						|| (offset_y != 0 && self.vertical != Boundary::Periodic);
+					df.set_border_offset(index, (offset_x, offset_y), is_virtual);
+				}
+			}
+		}
+		df
+	}
+
+	fn minimizes(&self, distance_type: DistanceType, spacing: &Spacing) -> bool {
+		self.processor.minimizes(distance_type, spacing)
+	}
+
+	fn max_distance(&self) -> Option<f64> {
+		self.processor.max_distance()
+	}
+
+	fn supports_spacing(&self, spacing: &Spacing) -> bool {
+		self.processor.supports_spacing(spacing)
+	}
+}
+
+/// The number of cells the field is extended by on each side of an axis.
+/// For periodic boundaries the nearest cell is at most half of the size away (around the border).
+fn margin(boundary: Boundary, size: u32) -> u32 {
+	match boundary {
+		Boundary::Ignore => 0,
+		Boundary::Periodic => size.div_ceil(2),
+		Boundary::Background | Boundary::Foreground => 1,
+	}
+}
+
+fn extended_size(size: u32, margin: u32) -> u32 {
+	match size.checked_add(margin).and_then(|size| size.checked_add(margin)) {
+		Some(extended) => extended,
+		None => panic!("field with size {} is too large to extend it by {} cells on each side", size, margin),
+	}
+}
+
+/// The position in the original field and the border offset for a coordinate of the extended field
+/// (relative to the original field). The offset fits into 32 bit, since the extended field does.
+fn field_coordinate(boundary: Boundary, coordinate: i64, size: u32) -> (u32, i32) {
+	let position = if boundary == Boundary::Periodic {
+		coordinate.rem_euclid(size as i64)
+	} else {
+		coordinate.max(0).min(size as i64 - 1)
+	};
+	(position as u32, (coordinate - position) as i32)
+}
+
+/// The coordinate in the original field for a coordinate of the extended field.
+/// For coordinates beyond a constant border this is the value (true for foreground) of the border instead.
+fn source_coordinate(boundary: Boundary, coordinate: i64, size: u32) -> Result<u32, bool> {
+	if coordinate >= 0 && coordinate < size as i64 {
+		return Ok(coordinate as u32);
+	}
+	match boundary {
+		Boundary::Periodic => Ok(coordinate.rem_euclid(size as i64) as u32),
+		Boundary::Background => Err(false),
+		Boundary::Foreground => Err(true),
+		Boundary::Ignore => panic!("the field is not extended beyond an ignored border"),
+	}
+}
@@ -0,0 +1,146 @@
+use crate::data::{DistanceField, NO_NEAREST_CELL, pack_coordinates};
+use crate::data::chamfer::{ChamferField, NO_DISTANCE};
+use crate::data::input::{InputField, Spacing};
+use crate::distance::DistanceType;
+use crate::processor::SourceProcessor;
+
+/// The integer weighted mask of a chamfer distance transformation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChamferMask {
+	/// Horizontal and vertical steps with a weight of one.
+	/// The distances are the exact rectilinear (Manhattan) distances.
+	CityBlock,
+
+	/// Horizontal, vertical and diagonal steps with a weight of one.
+	/// The distances are the exact Chebyshev (chessboard) distances.
+	Chessboard,
+
+	/// Horizontal and vertical steps with a weight of 3, diagonal steps with a weight of 4.
+	ThreeFour,
+
+	/// Horizontal and vertical steps with a weight of 5, diagonal steps with a weight of 7
+	/// and knight's moves (two cells on one axis, one on the other) with a weight of 11.
+	FiveSevenEleven,
+}
+
+impl ChamferMask {
+	/// The mask, that calculates the exact distances of the given type.
+	/// This is `None` for distance types without an exact chamfer mask (e.g. the euclidean distance).
+	pub fn for_distance_type(distance_type: DistanceType) -> Option<Self> {
+		match distance_type {
+			DistanceType::RectilinearDistance => Some(ChamferMask::CityBlock),
+			DistanceType::ChebyshevDistance => Some(ChamferMask::Chessboard),
+			_ => None,
+		}
+	}
+
+	/// The weight of a horizontal or vertical step, i.e. the weighted distance of one cell.
+	pub fn unit(&self) -> u32 {
+		match self {
+			ChamferMask::CityBlock | ChamferMask::Chessboard => 1,
+			ChamferMask::ThreeFour => 3,
+			ChamferMask::FiveSevenEleven => 5,
+		}
+	}
+
+	/// The offsets (with their weights) of the first half of the mask,
+	/// i.e. the neighbours that precede the center cell in row-major order.
+	fn forward_half(&self) -> Vec<(i64, i64, u32)> {
+		let (axial, diagonal) = match self {
+			ChamferMask::CityBlock => (1, None),
+			ChamferMask::Chessboard => (1, Some(1)),
+			ChamferMask::ThreeFour => (3, Some(4)),
+			ChamferMask::FiveSevenEleven => (5, Some(7)),
+		};
+		let mut offsets = vec![(-1, 0, axial), (0, -1, axial)];
+		if let Some(diagonal) = diagonal {
+			offsets.extend_from_slice(&[(-1, -1, diagonal), (1, -1, diagonal)]);
+		}
+		if *self == ChamferMask::FiveSevenEleven {
+			offsets.extend_from_slice(&[(-2, -1, 11), (2, -1, 11), (-1, -2, 11), (1, -2, 11)]);
+		}
+		offsets
+	}
+}
+
+/// Calculates chamfer distances with the classic two pass algorithm: the first pass propagates
+/// the distances with the first half of the mask from the top left to the bottom right corner,
+/// the second pass with the other half back to the top left corner.
+///
+/// Every cell also keeps the nearest cell of the other layer the distance was propagated from.
+/// For the city block and the chessboard mask these are the exact nearest cells under
+/// the rectilinear and the Chebyshev distance (see `ChamferMask::for_distance_type`), so the distances
+/// of the `DistanceTransformation` for these distance types are exact as well.
+/// The masks measure distances in cells, so input fields must have the default spacing (see `Spacing`).
+pub struct ChamferProcessor {
+	mask: ChamferMask,
+}
+
+impl ChamferProcessor {
+	pub fn new(mask: ChamferMask) -> Self {
+		ChamferProcessor {
+			mask,
+		}
+	}
+
+	/// Calculate the integer distances and the nearest cells of all cells.
+	pub fn process_chamfer(&self, field: &InputField) -> ChamferField {
+		if !field.spacing.is_unit() {
+			panic!("chamfer masks require a spacing of one on both axes (got {}x{})", field.spacing.x, field.spacing.y);
+		}
+		let (w, h) = (field.width as i64, field.height as i64);
+		// the layers are unpacked, since reading single bits is considerably slower
+		let layers: Vec<bool> = field.data.iter().copied().collect();
+		let mut distances = vec![NO_DISTANCE; layers.len()];
+		let mut nearest = vec![NO_NEAREST_CELL; layers.len()];
+
+		let forward = self.mask.forward_half();
+		let backward: Vec<(i64, i64, u32)> = forward.iter().map(|(dx, dy, weight)| (-dx, -dy, *weight)).collect();
+
+		let mut propagate = |index: usize, offsets: &[(i64, i64, u32)]| {
+			let (x, y) = (index as i64 % w, index as i64 / w);
+			for (dx, dy, weight) in offsets {
+				let (nx, ny) = (x + dx, y + dy);
+				if nx < 0 || ny < 0 || nx >= w || ny >= h {
+					continue;
+				}
+				let neighbour = (nx + ny * w) as usize;
+				// a neighbour of the other layer is a nearest cell itself, otherwise its nearest cell is the candidate
+				let (distance, candidate) = if layers[neighbour] != layers[index] {
+					(*weight, pack_coordinates(nx as u32, ny as u32))
+				} else if distances[neighbour] != NO_DISTANCE {
+					(distances[neighbour].saturating_add(*weight), nearest[neighbour])
+				} else {
+					continue;
+				};
+				if distance < distances[index] {
+					distances[index] = distance;
+					nearest[index] = candidate;
+				}
+			}
+		};
+		for index in 0..layers.len() {
+			propagate(index, &forward);
+		}
+		for index in (0..layers.len()).rev() {
+			propagate(index, &backward);
+		}
+
+		ChamferField::new(field.width, field.height, self.mask, field.data.clone(), distances, nearest)
+	}
+}
+
+impl SourceProcessor for ChamferProcessor {
+	fn process(&self, field: &InputField) -> DistanceField {
+		DistanceField::from(&self.process_chamfer(field))
+	}
+
+	/// Only fields with the default spacing are supported.
+	fn minimizes(&self, distance_type: DistanceType, spacing: &Spacing) -> bool {
+		ChamferMask::for_distance_type(distance_type) == Some(self.mask) && spacing.is_unit()
+	}
+
+	fn supports_spacing(&self, spacing: &Spacing) -> bool {
+		spacing.is_unit()
+	}
+}
@@ -0,0 +1,96 @@
+use std::collections::BinaryHeap;
+
+use crate::data::input::{InputField, Spacing};
+use crate::data::scalar::ScalarField;
+use crate::processor::queue::QueuedCell;
+
+/// Calculates the arrival times of a front, that starts at the foreground cells of an input field
+/// and travels with the speed of each cell, with the Fast Marching Method (solving the eikonal
+/// equation |∇T| = 1 / speed with first order upwind differences).
+///
+/// With a speed of one everywhere, the arrival time approximates the euclidean distance to the
+/// foreground (in physical units, see `Spacing`). Cells with a speed of zero (or less) are never
+/// reached, so they are obstacles for the front. A cost map can be used as well, since the cost
+/// of a cell is one divided by its speed.
+pub struct FastMarchingProcessor;
+
+impl FastMarchingProcessor {
+	/// Calculate the arrival time of every cell, the time is zero for the foreground cells
+	/// and infinite for cells that can not be reached.
+	pub fn process(&self, seeds: &InputField, speed: &ScalarField) -> ScalarField {
+		if seeds.width != speed.width || seeds.height != speed.height {
+			panic!("speed map of size {}x{} does not match the input of size {}x{}", speed.width, speed.height, seeds.width, seeds.height);
+		}
+		let (w, h) = (seeds.width as i64, seeds.height as i64);
+		let mut times = vec![f64::INFINITY; seeds.data.len()];
+		let mut accepted = vec![false; seeds.data.len()];
+		let mut queue = BinaryHeap::new();
+		for (index, is_seed) in seeds.data.iter().enumerate() {
+			if *is_seed {
+				times[index] = 0.0;
+				queue.push(QueuedCell { distance: 0.0, index });
+			}
+		}
+
+		while let Some(QueuedCell { distance, index }) = queue.pop() {
+			if accepted[index] || distance > times[index] {
+				continue;
+			}
+			accepted[index] = true;
+			let (x, y) = (index as i64 % w, index as i64 / w);
+			for (dx, dy) in [(0, -1), (-1, 0), (1, 0), (0, 1)] {
+				let (nx, ny) = (x + dx, y + dy);
+				if nx < 0 || ny < 0 || nx >= w || ny >= h {
+					continue;
+				}
+				let neighbour = (nx + ny * w) as usize;
+				let cell_speed = speed.data[neighbour] as f64;
+				if accepted[neighbour] || cell_speed <= 0.0 || !cell_speed.is_finite() {
+					continue;
+				}
+				let candidate = arrival_time(&times, &accepted, &seeds.spacing, (w, h), (nx, ny), 1.0 / cell_speed);
+				if candidate < times[neighbour] {
+					times[neighbour] = candidate;
+					queue.push(QueuedCell { distance: candidate, index: neighbour });
+				}
+			}
+		}
+
+		ScalarField::new(times.iter().map(|time| *time as f32).collect(), seeds.width, seeds.height)
+	}
+}
+
+/// The upwind solution of the eikonal equation for a cell from the accepted times of its neighbours.
+/// The cost is the time to travel one physical unit, so a step on the x-axis costs `cost * spacing.x`.
+fn arrival_time(times: &[f64], accepted: &[bool], spacing: &Spacing, (w, h): (i64, i64), (x, y): (i64, i64), cost: f64) -> f64 {
+	let time = |x: i64, y: i64| {
+		if x < 0 || y < 0 || x >= w || y >= h || !accepted[(x + y * w) as usize] {
+			f64::INFINITY
+		} else {
+			times[(x + y * w) as usize]
+		}
+	};
+	let horizontal = time(x - 1, y).min(time(x + 1, y));
+	let vertical = time(x, y - 1).min(time(x, y + 1));
+	let one_sided = (horizontal + cost * spacing.x).min(vertical + cost * spacing.y);
+	if !horizontal.is_finite() || !vertical.is_finite() {
+		return one_sided;
+	}
+
+	// solve ((t - horizontal) / spacing.x)² + ((t - vertical) / spacing.y)² = cost²,
+	// the solution is only valid, if the front comes from both directions
+	let (wx, wy) = (1.0 / (spacing.x * spacing.x), 1.0 / (spacing.y * spacing.y));
+	let a = wx + wy;
+	let b = -2.0 * (horizontal * wx + vertical * wy);
+	let c = horizontal * horizontal * wx + vertical * vertical * wy - cost * cost;
+	let discriminant = b * b - 4.0 * a * c;
+	if discriminant < 0.0 {
+		return one_sided;
+	}
+	let time = (-b + discriminant.sqrt()) / (2.0 * a);
+	if time >= horizontal.max(vertical) {
+		time
+	} else {
+		one_sided
+	}
+}
@@ -0,0 +1,90 @@
+use std::collections::BinaryHeap;
+
+use crate::data::{NO_NEAREST_CELL, pack_coordinates};
+use crate::data::geodesic::{OctileGeodesicField, ObstacleInputField};
+use crate::data::input::Spacing;
+use crate::processor::queue::QueuedCell;
+
+/// Calculates the octile geodesic distances of an input field with obstacles (see `OctileGeodesicField`)
+/// with Dijkstra's algorithm on the grid of cells. Steps only lead to the eight neighbours, so the
+/// distances are octile distances rather than euclidean distances (see `OctileGeodesicField`).
+///
+/// The cells of each layer are processed separately, starting from all cells of the other layer.
+/// Diagonal steps may not cut the corner of an obstacle, so a diagonal line of obstacles is a closed wall.
+pub struct OctileGeodesicProcessor;
+
+impl OctileGeodesicProcessor {
+	pub fn process(&self, input: &ObstacleInputField) -> OctileGeodesicField {
+		let field = &input.field;
+		let len = field.data.len();
+		let mut distances = vec![f64::INFINITY; len];
+		let mut predecessors = vec![NO_NEAREST_CELL; len];
+		// the layers and obstacles are unpacked, since reading single bits is considerably slower
+		let layers: Vec<bool> = field.data.iter().copied().collect();
+		let obstacles: Vec<bool> = input.obstacles.iter().copied().collect();
+
+		for target_layer in [false, true] {
+			process_layer(field.width, &field.spacing, &layers, &obstacles, target_layer, &mut distances, &mut predecessors);
+		}
+
+		OctileGeodesicField::new(field.width, field.height, field.spacing, field.data.clone(), input.obstacles.clone(), distances, predecessors)
+	}
+}
+
+/// Calculate the distances of all cells of the target layer (true for foreground).
+fn process_layer(width: u32,
+				 spacing: &Spacing,
+				 layers: &[bool],
+				 obstacles: &[bool],
+				 target_layer: bool,
+				 distances: &mut [f64],
+				 predecessors: &mut [u64]) {
+	let (w, h) = (width as i64, (layers.len() / width as usize) as i64);
+	let diagonal = spacing.distance_squared(1, 1).sqrt();
+	let is_open = |x: i64, y: i64| x >= 0 && y >= 0 && x < w && y < h && !obstacles[(x + y * w) as usize];
+
+	// the distances of the other layer are only used as the starting points of the paths
+	let mut current = vec![f64::INFINITY; layers.len()];
+	let mut queue = BinaryHeap::new();
+	for (index, is_foreground) in layers.iter().enumerate() {
+		if *is_foreground != target_layer && !obstacles[index] {
+			current[index] = 0.0;
+			queue.push(QueuedCell { distance: 0.0, index });
+		}
+	}
+
+	while let Some(QueuedCell { distance, index }) = queue.pop() {
+		if distance > current[index] {
+			continue;
+		}
+		let (x, y) = (index as i64 % w, index as i64 / w);
+		for (dx, dy) in NEIGHBOURS.iter() {
+			let (nx, ny) = (x + dx, y + dy);
+			if !is_open(nx, ny) {
+				continue;
+			}
+			// diagonal steps must not pass between two cells, if one of them is an obstacle
+			if *dx != 0 && *dy != 0 && (!is_open(x + dx, y) || !is_open(x, y + dy)) {
+				continue;
+			}
+			let neighbour = (nx + ny * w) as usize;
+			if layers[neighbour] != target_layer {
+				continue;
+			}
+			let step = match (dx, dy) {
+				(0, _) => spacing.y,
+				(_, 0) => spacing.x,
+				_ => diagonal,
+			};
+			let candidate = distance + step;
+			if candidate < current[neighbour] {
+				current[neighbour] = candidate;
+				distances[neighbour] = candidate;
+				predecessors[neighbour] = pack_coordinates(x as u32, y as u32);
+				queue.push(QueuedCell { distance: candidate, index: neighbour });
+			}
+		}
+	}
+}
+
+const NEIGHBOURS: [(i64, i64); 8] = [(0, -1), (-1, 0), (1, 0), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)];
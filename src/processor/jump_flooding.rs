@@ -0,0 +1,155 @@
+use crate::data::{Cell, DistanceField, FieldCoordinates, NO_NEAREST_CELL, pack_coordinates, unpack_coordinates};
+use crate::data::input::InputField;
+use crate::processor::SourceProcessor;
+
+/// Calculates the nearest cells with the Jump Flooding Algorithm (JFA), as it is usually done on the GPU:
+/// every pass looks at the nearest cells of the eight neighbours at a distance of the step size
+/// (and at its own nearest cell) and keeps the closest one. The step size starts at half of the
+/// next power of two of the larger field dimension and is halved after each pass down to one.
+///
+/// With the refinement (enabled by default) an additional pass with a step size of one runs before
+/// the regular passes (1+JFA), which fixes most of the errors of the plain algorithm.
+/// Like the other processors, JFA is not exact, a few cells can end up with a nearest cell,
+/// that is slightly farther away than the real one.
+///
+/// The results are deterministic, so they can be compared with a GPU implementation, that follows the same rules.
+/// They are only identical, if the GPU compares the distances with the same arithmetic: for the default spacing
+/// the squared distances are compared as exact integers, while a shader with float distances can round nearly
+/// equal distances differently (especially for large fields), so single cells can get a different nearest cell.
+/// The rules are:
+/// - every pass reads the nearest cells of the previous pass only (ping-pong buffers),
+/// - the candidates of a cell are visited in the order: own nearest cell, then the neighbours
+///   row by row from the top left (dy = -step, 0, step; dx = -step, 0, step),
+/// - a candidate only replaces the current nearest cell, if it is strictly closer.
+///
+/// The foreground and the background are flooded separately, so every cell gets the nearest cell
+/// of the other layer. The distances are compared in physical units (see `Spacing`).
+pub struct JumpFloodingProcessor {
+	refinement: bool,
+}
+
+impl JumpFloodingProcessor {
+	pub fn new() -> Self {
+		JumpFloodingProcessor {
+			refinement: true,
+		}
+	}
+
+	/// Enable or disable the additional pass with a step size of one before the regular passes (1+JFA).
+	pub fn refinement(mut self, enabled: bool) -> Self {
+		self.refinement = enabled;
+		self
+	}
+
+	/// The step sizes of all passes for a field of the given size, in the order of the passes.
+	pub fn steps(&self, width: u32, height: u32) -> Vec<u32> {
+		let mut steps = Vec::new();
+		if self.refinement {
+			steps.push(1);
+		}
+		let mut step = (width.max(height) as u64).next_power_of_two() / 2;
+		while step >= 1 {
+			steps.push(step as u32);
+			step /= 2;
+		}
+		steps
+	}
+}
+
+impl Default for JumpFloodingProcessor {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SourceProcessor for JumpFloodingProcessor {
+	fn process(&self, field: &InputField) -> DistanceField {
+		let mut df = DistanceField::new(field);
+		// the layers are unpacked, since reading single bits is considerably slower
+		let layers: Vec<bool> = field.data.iter().copied().collect();
+		let steps = self.steps(field.width, field.height);
+		let spacing = field.spacing;
+
+		let flood_layer = |seed_layer: bool| {
+			if spacing.is_unit() {
+				flood(&layers, seed_layer, field.width, field.height, &steps, |x, y, packed| {
+					let (nearest_x, nearest_y) = unpack_coordinates(packed);
+					Cell::get_distance_squared(&x, &y, &nearest_x, &nearest_y)
+				})
+			} else {
+				flood(&layers, seed_layer, field.width, field.height, &steps, |x, y, packed| {
+					let (nearest_x, nearest_y) = unpack_coordinates(packed);
+					spacing.distance_squared(x.abs_diff(nearest_x), y.abs_diff(nearest_y))
+				})
+			}
+		};
+		let nearest_foreground = flood_layer(true);
+		let nearest_background = flood_layer(false);
+
+		for (index, is_foreground) in layers.iter().enumerate() {
+			let nearest = if *is_foreground { nearest_background[index] } else { nearest_foreground[index] };
+			if nearest != NO_NEAREST_CELL {
+				let (x, y) = unpack_coordinates(nearest);
+				df.set_nearest_cell(index, x, y);
+			}
+		}
+		df
+	}
+}
+
+/// Floods the field from all cells of the seed layer and returns the packed coordinates
+/// of the nearest seed of every cell (`NO_NEAREST_CELL`, if the field has no seeds).
+fn flood<D: PartialOrd>(
+	layers: &[bool],
+	seed_layer: bool,
+	width: u32,
+	height: u32,
+	steps: &[u32],
+	distance: impl Fn(u32, u32, u64) -> D) -> Vec<u64> {
+
+	let coordinates = FieldCoordinates::new(width);
+	let mut current: Vec<u64> = layers.iter()
+		.enumerate()
+		.map(|(index, layer)| {
+			if *layer == seed_layer {
+				let (x, y) = coordinates.of(index);
+				pack_coordinates(x, y)
+			} else {
+				NO_NEAREST_CELL
+			}
+		})
+		.collect();
+	let mut next = current.clone();
+	let (w, h) = (width as i64, height as i64);
+
+	for step in steps {
+		let step = *step as i64;
+		for y in 0..h {
+			for x in 0..w {
+				let index = (x + y * w) as usize;
+				let mut best = current[index];
+				let mut best_distance = if best == NO_NEAREST_CELL { None } else { Some(distance(x as u32, y as u32, best)) };
+				for dy in [-step, 0, step] {
+					for dx in [-step, 0, step] {
+						let (nx, ny) = (x + dx, y + dy);
+						if (dx == 0 && dy == 0) || nx < 0 || ny < 0 || nx >= w || ny >= h {
+							continue;
+						}
+						let candidate = current[(nx + ny * w) as usize];
+						if candidate == NO_NEAREST_CELL || candidate == best {
+							continue;
+						}
+						let candidate_distance = distance(x as u32, y as u32, candidate);
+						if best_distance.as_ref().is_none_or(|best_distance| candidate_distance < *best_distance) {
+							best = candidate;
+							best_distance = Some(candidate_distance);
+						}
+					}
+				}
+				next[index] = best;
+			}
+		}
+		std::mem::swap(&mut current, &mut next);
+	}
+	current
+}
@@ -0,0 +1,54 @@
+use crate::data::{Cell, CellPosition};
+use crate::data::label::{LabelField, LabelInputField, LabeledCell};
+use crate::processor::sweep::sweep;
+
+/// Calculates the nearest seed cell (and its label) for all cells of a label input field
+/// with the same eight side sweep that is used for distance fields.
+/// Other than for distance fields, the nearest cells are not taken from the opposite layer
+/// but from all seed cells, so any number of labels can be processed at once.
+pub struct LabelSweepProcessor;
+
+impl LabelSweepProcessor {
+	pub fn process(&self, field: &LabelInputField) -> LabelField {
+		let w = field.width as usize;
+		let mut cells: Vec<LabeledCell> = field.data.iter()
+			.enumerate()
+			.map(|(index, label)| {
+				let (x, y) = ((index % w) as u32, (index / w) as u32);
+				LabeledCell {
+					label: *label,
+					is_seed: label.is_some(),
+					x,
+					y,
+					nearest_seed_position: label.map(|_| CellPosition { x, y, index: index as u64 }),
+				}
+			})
+			.collect();
+
+		sweep(field.width, field.height, |target_index, source_index| {
+			let source = &cells[source_index];
+			let candidate = match source.nearest_seed_position {
+				Some(candidate) => candidate,
+				None => return,
+			};
+			let label = source.label;
+			let target = &cells[target_index];
+			let candidate_distance = Cell::get_distance_squared(&target.x, &target.y, &candidate.x, &candidate.y);
+			let is_nearer = match target.distance_to_nearest_squared() {
+				None => true,
+				Some(existing_distance) => candidate_distance < existing_distance,
+			};
+			if is_nearer {
+				let target = &mut cells[target_index];
+				target.nearest_seed_position = Some(candidate);
+				target.label = label;
+			}
+		});
+
+		LabelField {
+			data: cells,
+			width: field.width,
+			height: field.height,
+		}
+	}
+}
@@ -0,0 +1,119 @@
+use crate::data::{CellPosition, DistanceField};
+use crate::data::input::{InputField, Spacing};
+use crate::data::narrow_band::NarrowBandField;
+use crate::distance::DistanceType;
+use crate::processor::SourceProcessor;
+
+/// Calculates the exact nearest cells of all cells within a maximum distance to the boundary
+/// between foreground and background. Cells farther away are marked as far cells (without nearest cell).
+///
+/// A summed area table of the foreground cells tells in constant time, if there is any cell of
+/// the opposite layer within the maximum distance, so far cells cost no further work.
+/// For all other cells the neighbours are searched in the order of their distance.
+pub struct NarrowBandProcessor {
+	max_distance: f32,
+}
+
+impl NarrowBandProcessor {
+	pub fn new(max_distance: f32) -> Self {
+		if max_distance < 1.0 {
+			panic!("maximum distance must be at least one cell (got {})", max_distance);
+		}
+		NarrowBandProcessor {
+			max_distance,
+		}
+	}
+
+	/// Calculate the narrow band field, this never allocates the nearest cells of far cells.
+	pub fn process_band(&self, field: &InputField) -> NarrowBandField {
+		let w = field.width as usize;
+		let h = field.height as usize;
+		let spacing = field.spacing;
+		let (radius_x, radius_y) = self.radius(&spacing);
+		let offsets = self.offsets(&spacing);
+
+		// summed area table of the foreground cells (with an additional leading row and column)
+		let mut table = vec![0u32; (w + 1) * (h + 1)];
+		for y in 0..h {
+			let mut row_sum = 0;
+			for x in 0..w {
+				if field.data[x + y * w] {
+					row_sum += 1;
+				}
+				table[(x + 1) + (y + 1) * (w + 1)] = table[(x + 1) + y * (w + 1)] + row_sum;
+			}
+		}
+
+		let mut band = Vec::new();
+		for y in 0..h as i64 {
+			for x in 0..w as i64 {
+				let is_foreground = field.data[x as usize + y as usize * w];
+
+				// is there any cell of the opposite layer within the square around the cell?
+				let (left, top) = ((x - radius_x).max(0) as usize, (y - radius_y).max(0) as usize);
+				let (right, bottom) = ((x + radius_x + 1).min(w as i64) as usize, (y + radius_y + 1).min(h as i64) as usize);
+				let foreground = table[right + bottom * (w + 1)] + table[left + top * (w + 1)]
+					- table[left + bottom * (w + 1)] - table[right + top * (w + 1)];
+				let area = ((right - left) * (bottom - top)) as u32;
+				let has_opposite = if is_foreground { foreground < area } else { foreground > 0 };
+				if !has_opposite {
+					continue;
+				}
+
+				let nearest = offsets.iter()
+					.map(|(dx, dy)| (x + dx, y + dy))
+					.find(|(nx, ny)| *nx >= 0 && *ny >= 0 && *nx < w as i64 && *ny < h as i64
+						&& field.data[*nx as usize + *ny as usize * w] != is_foreground);
+				if let Some((nx, ny)) = nearest {
+					band.push(((x as usize + y as usize * w) as u64, CellPosition {
+						x: nx as u32,
+						y: ny as u32,
+						index: (nx as usize + ny as usize * w) as u64,
+					}));
+				}
+			}
+		}
+
+		NarrowBandField::new(field.width, field.height, self.max_distance, spacing, field.data.clone(), band)
+	}
+
+	/// The number of cells within the maximum distance (in physical units) on the x- and on the y-axis.
+	pub(crate) fn radius(&self, spacing: &Spacing) -> (i64, i64) {
+		let max_distance = self.max_distance as f64;
+		((max_distance / spacing.x).floor() as i64, (max_distance / spacing.y).floor() as i64)
+	}
+
+	/// All offsets within the maximum distance, sorted by their (physical) distance.
+	/// Offsets with the same distance are sorted by row and column, so the result is deterministic.
+	fn offsets(&self, spacing: &Spacing) -> Vec<(i64, i64)> {
+		let (radius_x, radius_y) = self.radius(spacing);
+		let max_squared = self.max_distance as f64 * self.max_distance as f64;
+		let mut offsets = Vec::new();
+		for dy in -radius_y..=radius_y {
+			for dx in -radius_x..=radius_x {
+				let squared = spacing.distance_squared(dx.unsigned_abs() as u32, dy.unsigned_abs() as u32);
+				if (dx, dy) != (0, 0) && squared <= max_squared {
+					offsets.push((squared, dy, dx));
+				}
+			}
+		}
+		offsets.sort_by(|first, second| first.partial_cmp(second).unwrap());
+		offsets.into_iter().map(|(_, dy, dx)| (dx, dy)).collect()
+	}
+}
+
+impl SourceProcessor for NarrowBandProcessor {
+	fn process(&self, field: &InputField) -> DistanceField {
+		DistanceField::from(&self.process_band(field))
+	}
+
+	/// The nearest cells within the maximum distance are the exact nearest cells under the
+	/// euclidean distance (in physical units), far cells have no nearest cell.
+	fn minimizes(&self, distance_type: DistanceType, _spacing: &Spacing) -> bool {
+		!matches!(distance_type, DistanceType::ChebyshevDistance | DistanceType::RectilinearDistance)
+	}
+
+	fn max_distance(&self) -> Option<f64> {
+		Some(self.max_distance as f64)
+	}
+}
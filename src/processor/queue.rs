@@ -0,0 +1,27 @@
+use std::cmp::Ordering;
+
+/// A cell in the priority queue, the cell with the smallest distance (and then the smallest index) comes first.
+pub(crate) struct QueuedCell {
+	pub distance: f64,
+	pub index: usize,
+}
+
+impl Ord for QueuedCell {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other.distance.total_cmp(&self.distance).then_with(|| other.index.cmp(&self.index))
+	}
+}
+
+impl PartialOrd for QueuedCell {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for QueuedCell {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for QueuedCell {}
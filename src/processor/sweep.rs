@@ -1,4 +1,5 @@
-use crate::data::{Cell, CellLayer, DistanceField, CellPosition};
+use crate::data::{DistanceField, FieldCoordinates, NearestCells, NearestIndex};
+use crate::data::input::Spacing;
 use crate::processor::SourceProcessor;
 use crate::data::input::InputField;
 
@@ -6,249 +7,208 @@ pub struct EightSideSweepProcessor;
 
 impl SourceProcessor for EightSideSweepProcessor {
 	fn process(&self, field: &InputField) -> DistanceField {
-		let mut buffer = self.init_buffer_for_distance_field(&field);
-		self.sweep_buffer(&mut buffer, field.width, field.height);
-		self.get_distance_field_from_buffer(&buffer, field.width, field.height)
+		let mut df = DistanceField::new(field);
+		self.sweep_field(&mut df);
+		df
 	}
 }
 
 impl EightSideSweepProcessor {
-	/// Initialize an inner buffer with cells to calculate the distance field.
-	fn init_buffer_for_distance_field(&self, source: &InputField) -> Vec<Cell> {
-		source.data
-			.chunks(source.width as usize)
-			.enumerate()
-			.map(|(row_index, row_data)| {
-				row_data.iter().enumerate().map(move |(col_index, value)| {
-					Cell {
-						index: (col_index + row_index * source.width as usize) as u32,
-						x: col_index as u16,
-						y: row_index as u16,
-						layer: match value {
-							true => CellLayer::Foreground,
-							false => CellLayer::Background,
-						},
-						nearest_cell_position: None,
-					}
-				})
-			})
-			.flatten()
-			.collect()
+	/// 2-pass sweep over the cells of the field to calculate the distances.
+	fn sweep_field(&self, df: &mut DistanceField) {
+		// the layers are unpacked for the sweep, since reading single bits is considerably slower
+		let layers: Vec<bool> = df.layers.iter().copied().collect();
+		let coordinates = FieldCoordinates::new(df.width);
+		let (width, height, spacing) = (df.width, df.height, df.spacing);
+		match &mut df.nearest {
+			NearestCells::Compact(nearest) => self.sweep_nearest(&layers, nearest, coordinates, width, height, spacing),
+			NearestCells::Wide(nearest) => self.sweep_nearest(&layers, nearest, coordinates, width, height, spacing),
+		}
 	}
 
-	/// 2-pass sweep over the inner buffer to calculate the distances.
-	fn sweep_buffer(&self, buffer: &mut Vec<Cell>, field_width: u16, field_height: u16) {
-		// Two pass sweep (down + up)
-		self.sweep_buffer_down(buffer, field_width, field_height);
-		self.sweep_buffer_up(buffer, field_width, field_height);
+	fn sweep_nearest<T: NearestIndex>(
+		&self,
+		layers: &[bool],
+		nearest: &mut [T],
+		coordinates: FieldCoordinates,
+		width: u32,
+		height: u32,
+		spacing: Spacing) {
+
+		if spacing.is_unit() {
+			sweep(width, height, |target_index, source_index| {
+				self.compare_cells(layers, nearest, coordinates, target_index, source_index, |dx, dy| {
+					dx as u64 * dx as u64 + dy as u64 * dy as u64
+				})
+			});
+		} else {
+			// the distances are compared in physical units, so the nearest cells depend on the spacing
+			sweep(width, height, |target_index, source_index| {
+				self.compare_cells(layers, nearest, coordinates, target_index, source_index, |dx, dy| spacing.distance_squared(dx, dy))
+			});
+		}
 	}
 
-	/// Down sweep (pass #1)
-	fn sweep_buffer_down(&self, buffer: &mut Vec<Cell>, field_width: u16, field_height: u16) {
-		let w = field_width as usize;
-		let h = field_height as usize;
-
-		// first row
-
-		// sweep to the right (left)
-		//
-		//      *O- -->
-		//
-		for index in 1..w {
-			self.compare_cells(buffer, index, index - 1); // left
+	fn compare_cells<T: NearestIndex, D: PartialOrd>(
+		&self,
+		layers: &[bool],
+		nearest: &mut [T],
+		coordinates: FieldCoordinates,
+		target_index: usize,
+		source_index: usize,
+		distance: impl Fn(u32, u32) -> D) {
+
+		// if the cells have a different layer, we are on the boundary between foreground
+		// and background, so the source cell itself is a candidate for the nearest cell of
+		// our target cell. Otherwise the cells are on the same layer and the nearest cell
+		// of the source (if it has one) is the candidate.
+		let candidate = if layers[target_index] != layers[source_index] {
+			T::from_index(source_index)
+		} else {
+			nearest[source_index]
 		};
+		let existing = nearest[target_index];
+		if candidate == T::NONE || candidate == existing {
+			return;
+		}
 
-		// sweep to the left (right)
-		//
-		// <--  -O*
-		//
-		for index in (0..w - 1).rev() {
-			self.compare_cells(buffer, index, index + 1); // right
+		// the candidate is only taken, if the target has no nearer cell yet. This also applies to
+		// neighbours of the other layer: always taking them let a diagonal neighbour visited later
+		// replace a direct neighbour, so cells next to the boundary got a distance of √2 instead of one
+		let is_nearer = existing == T::NONE || {
+			let (x, y) = coordinates.of(target_index);
+			let (candidate_x, candidate_y) = coordinates.of(candidate.index());
+			let (existing_x, existing_y) = coordinates.of(existing.index());
+			distance(x.abs_diff(candidate_x), y.abs_diff(candidate_y)) < distance(x.abs_diff(existing_x), y.abs_diff(existing_y))
 		};
+		if is_nearer {
+			nearest[target_index] = candidate;
+		}
+	}
+}
 
-		let mut idx = w;
+/// Visits the cells of a field in the order of the 2-pass eight side sweep (down + up).
+/// For every visited pair of neighbours the compare function is called with the index of
+/// the target cell and the index of the source cell, whose information may be propagated to the target.
+pub(crate) fn sweep(field_width: u32, field_height: u32, mut compare: impl FnMut(usize, usize)) {
+	sweep_down(field_width, field_height, &mut compare);
+	sweep_up(field_width, field_height, &mut compare);
+}
 
-		// other rows
-		for _ in 1..h {
+/// Down sweep (pass #1)
+fn sweep_down(field_width: u32, field_height: u32, compare: &mut impl FnMut(usize, usize)) {
+	let w = field_width as usize;
+	let h = field_height as usize;
 
-			// sweep to the right
+	// first row
 
-			// first cell (top, top-right)
-			self.compare_cells(buffer, idx, idx - w); // top
-			self.compare_cells(buffer, idx, idx - w + 1); // top right
+	// sweep to the right (left)
+	//
+	//      *O- -->
+	//
+	for index in 1..w {
+		compare(index, index - 1); // left
+	};
 
-			idx += 1;
+	// sweep to the left (right)
+	//
+	// <--  -O*
+	//
+	for index in (0..w - 1).rev() {
+		compare(index, index + 1); // right
+	};
 
-			// row cells (except first and last)
-			for _ in 1..w - 1 {
-				self.compare_cells(buffer, idx, idx - 1); // left
-				self.compare_cells(buffer, idx, idx - w); // top
-				self.compare_cells(buffer, idx, idx - w - 1); // top left
-				self.compare_cells(buffer, idx, idx - w + 1); // top right
-				idx += 1;
-			}
-
-			// last cell (left, top-left, top)
-			self.compare_cells(buffer, idx, idx - 1); // left
-			self.compare_cells(buffer, idx, idx - w); // top
-			self.compare_cells(buffer, idx, idx - w - 1); // top left
-
-			// sweep to the left
-			for _ in 0..w - 1 {
-				idx -= 1;
-				self.compare_cells(buffer, idx, idx + 1); // right
-			};
-			idx += w;
-		}
-	}
+	let mut idx = w;
 
-	/// Up sweep (pass #2)
-	fn sweep_buffer_up(&self, buffer: &mut Vec<Cell>, field_width: u16, field_height: u16) {
-		let w = field_width as usize;
-		let h = field_height as usize;
+	// other rows
+	for _ in 1..h {
 
-		let mut idx = w * h - 1;
+		// sweep to the right
 
-		// last row
-		// sweep to the left (right)
-		for _ in 1..w {
-			idx -= 1;
-			self.compare_cells(buffer, idx, idx + 1); // right
-		}
-		// sweep to the right (left)
-		for _ in 1..w {
-			self.compare_cells(buffer, idx, idx - 1); // left
+		// first cell (top, top-right)
+		compare(idx, idx - w); // top
+		compare(idx, idx - w + 1); // top right
+
+		idx += 1;
+
+		// row cells (except first and last)
+		for _ in 1..w - 1 {
+			compare(idx, idx - 1); // left
+			compare(idx, idx - w); // top
+			compare(idx, idx - w - 1); // top left
+			compare(idx, idx - w + 1); // top right
 			idx += 1;
 		}
 
-		// other rows
-		for _ in 1..h {
-			idx -= w;
-
-			// sweep to the left
-			// first element (bottom, bottom-left)
-			self.compare_cells(buffer, idx, idx + w); // bottom
-			self.compare_cells(buffer, idx, idx + w - 1); // bottom left
-
-			for _ in 1..w - 1 {
-				// other elements (except first and last)
-				idx -= 1;
-
-				//      ...
-				// <--  .O*
-				//      ***
-				self.compare_cells(buffer, idx, idx + 1); // right
-				self.compare_cells(buffer, idx, idx + w); // bottom
-				self.compare_cells(buffer, idx, idx + w + 1); // bottom right
-				self.compare_cells(buffer, idx, idx + w - 1); // bottom left
-			}
-
-			// last element (right, bottom-right, bottom)
+		// last cell (left, top-left, top)
+		compare(idx, idx - 1); // left
+		compare(idx, idx - w); // top
+		compare(idx, idx - w - 1); // top left
+
+		// sweep to the left
+		for _ in 0..w - 1 {
 			idx -= 1;
-			self.compare_cells(buffer, idx, idx + 1); // right
-			self.compare_cells(buffer, idx, idx + w); // bottom
-			self.compare_cells(buffer, idx, idx + w + 1); // bottom right
-
-			// sweep to the right (left)
-			// ...
-			// *O.  -->
-			// ...
-			for _ in 1..w {
-				idx += 1;
-				self.compare_cells(buffer, idx, idx - 1);
-			}
-		}
+			compare(idx, idx + 1); // right
+		};
+		idx += w;
 	}
+}
 
-	fn compare_cells(
-		&self,
-		buffer: &mut Vec<Cell>,
-		target_index: usize,
-		source_index: usize) {
-		let mut nearest_pos: Option<CellPosition> = None; // TODO: continue here...
-
-		{
-			let target_cell = &buffer[target_index];
-			let source_cell = &buffer[source_index];
-
-			if target_cell.layer != source_cell.layer {
-
-				// the cells have a different layer, so we are on the boundary between
-				// foreground and background. In that case, we will set the source
-				// cell as the nearest cell in our target cell
-
-				nearest_pos = Some(CellPosition {
-					x: source_cell.x,
-					y: source_cell.y,
-					index: source_index as u32,
-				});
-
-				// target_cell.set_nearest_cell_position(source_cell.x, source_cell.y);
-
-				// TODO: check, if we can and should use:
-				// a) relative x/a offset here
-				// b) relative index offset here
-				// c) absolute index here
-			} else {
-
-				// the cells are on the same layer (foreground or background),
-				// so we should maybe check their distances
-
-				match target_cell.distance_to_nearest_squared() {
-					None => {
-
-						// our target cell does not have a nearest cell yet,
-						// so we should take the nearest cell of the source
-						// as a reference.
-
-						// does the source have a nearest cell?
-
-						if let Some(nearest) = source_cell.get_nearest_cell_position() {
-
-							// yes it has, so we set this as the targets nearest cell position
-							// target_cell.set_nearest_cell_position(x, y);
-							nearest_pos = Some(nearest.clone());
-						}
-					}
-
-					Some(existing_target_distance) => {
-
-						// our target already has a distance
-
-						// let's check, if the source also has a nearest cell
-						if let Some(source_nearest) = source_cell.get_nearest_cell_position() {
-
-							// yes it has, so let's calculate the distance from our target cell
-							// to the nearest cell of the source
-							let distance_to_sources_nearest_cell =
-								Cell::get_distance_squared(
-									&target_cell.x,
-									&target_cell.y,
-									&source_nearest.x,
-									&source_nearest.y); //
-
-							if distance_to_sources_nearest_cell < existing_target_distance {
-								// set the new target
-								// target_cell.set_nearest_cell_position(source_nearest_cell_x, source_nearest_cell_y)
-								nearest_pos = Some(source_nearest.clone());
-							}
-						}
-						// otherwise we do nothing
-					}
-				}
-			}
-		}
-		if let Some(nearest) = nearest_pos {
-			let target_cell = &mut buffer[target_index];
-			target_cell.set_nearest_cell_position(nearest);
-		}
+/// Up sweep (pass #2)
+fn sweep_up(field_width: u32, field_height: u32, compare: &mut impl FnMut(usize, usize)) {
+	let w = field_width as usize;
+	let h = field_height as usize;
+
+	let mut idx = w * h - 1;
+
+	// last row
+	// sweep to the left (right)
+	for _ in 1..w {
+		idx -= 1;
+		compare(idx, idx + 1); // right
 	}
+	// sweep to the right (left), starting with the second cell
+	// (the first cell has no left neighbour in this row)
+	for _ in 1..w {
+		idx += 1;
+		compare(idx, idx - 1); // left
+	}
+
+	// other rows
+	for _ in 1..h {
+		idx -= w;
+
+		// sweep to the left
+		// first element (bottom, bottom-left)
+		compare(idx, idx + w); // bottom
+		compare(idx, idx + w - 1); // bottom left
 
-	fn get_distance_field_from_buffer(&self, buffer: &[Cell], width: u16, height: u16) -> DistanceField {
-		DistanceField {
-			width,
-			height,
-			data: Vec::from(buffer),
+		for _ in 1..w - 1 {
+			// other elements (except first and last)
+			idx -= 1;
+
+			//      ...
+			// <--  .O*
+			//      ***
+			compare(idx, idx + 1); // right
+			compare(idx, idx + w); // bottom
+			compare(idx, idx + w + 1); // bottom right
+			compare(idx, idx + w - 1); // bottom left
+		}
+
+		// last element (right, bottom-right, bottom)
+		idx -= 1;
+		compare(idx, idx + 1); // right
+		compare(idx, idx + w); // bottom
+		compare(idx, idx + w + 1); // bottom right
+
+		// sweep to the right (left)
+		// ...
+		// *O.  -->
+		// ...
+		for _ in 1..w {
+			idx += 1;
+			compare(idx, idx - 1);
 		}
 	}
 }
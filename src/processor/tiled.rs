@@ -0,0 +1,73 @@
+use std::io;
+
+use crate::data::scalar::ScalarField;
+use crate::data::tile::{TileSink, TileSource};
+use crate::processor::narrow_band::NarrowBandProcessor;
+
+/// Processes a field tile by tile, so only a single tile (and its halo) has to be held in memory.
+/// The tiles can be streamed from a raw file (`RawInputFile`) or a png file (`PngTileSource`).
+///
+/// Every tile is read with a halo of the maximum distance around it and processed by a
+/// narrow band processor. Since all cells within the maximum distance of a tile cell are part
+/// of the halo, the result is identical to processing the whole field at once with a
+/// `NarrowBandProcessor` of the same maximum distance (`ScalarField::from(&processor.process_band(..))`):
+/// exact signed euclidean distances with far cells clamped to the maximum distance.
+/// It is not identical to the distances of the `EightSideSweepProcessor`, whose distances are
+/// not exact, and only the signed distances are written (the nearest cells are not kept).
+pub struct TiledProcessor {
+	processor: NarrowBandProcessor,
+	max_distance: f32,
+	tile_size: u32,
+}
+
+impl TiledProcessor {
+	pub fn new(max_distance: f32) -> Self {
+		TiledProcessor {
+			processor: NarrowBandProcessor::new(max_distance),
+			max_distance,
+			tile_size: 256,
+		}
+	}
+
+	/// The width and height of the tiles (without their halo), the default is 256.
+	pub fn tile_size(mut self, tile_size: u32) -> Self {
+		if tile_size == 0 {
+			panic!("tile size must be positive (got {})", tile_size);
+		}
+		self.tile_size = tile_size;
+		self
+	}
+
+	/// Read all tiles from the source, process them and write the signed distances to the sink.
+	pub fn process(&self, source: &mut impl TileSource, sink: &mut impl TileSink) -> io::Result<()> {
+		let (width, height) = (source.width(), source.height());
+		let spacing = source.spacing();
+		let halo_x = (self.max_distance as f64 / spacing.x).ceil() as u32;
+		let halo_y = (self.max_distance as f64 / spacing.y).ceil() as u32;
+		let tile_size = self.tile_size;
+
+		for tile_y in (0..height).step_by(tile_size as usize) {
+			for tile_x in (0..width).step_by(tile_size as usize) {
+				let tile_width = tile_size.min(width - tile_x);
+				let tile_height = tile_size.min(height - tile_y);
+
+				// the tile extended by the halo (as far as the field reaches)
+				let left = tile_x.saturating_sub(halo_x);
+				let top = tile_y.saturating_sub(halo_y);
+				let right = (tile_x + tile_width).saturating_add(halo_x).min(width);
+				let bottom = (tile_y + tile_height).saturating_add(halo_y).min(height);
+
+				let region = source.read_region(left, top, right - left, bottom - top)?;
+				let values = ScalarField::from(&self.processor.process_band(&region));
+
+				let mut data = Vec::with_capacity(tile_width as usize * tile_height as usize);
+				for y in tile_y - top..tile_y - top + tile_height {
+					let start = (tile_x - left + y * region.width) as usize;
+					data.extend_from_slice(&values.data[start..start + tile_width as usize]);
+				}
+				sink.write_region(tile_x, tile_y, &ScalarField::new(data, tile_width, tile_height))?;
+			}
+		}
+		Ok(())
+	}
+}
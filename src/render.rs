@@ -0,0 +1,169 @@
+//! CPU-based rendering of distance fields.
+//! The renderers are simple reference implementations of the fragment shaders
+//! that are typically used to display distance field textures. They can be used to
+//! preview and test generated assets without a GPU.
+
+use std::convert::TryFrom;
+
+use crate::data::sampling::FieldSampler;
+
+/// Rendering of the shapes encoded in a distance field.
+pub mod sdf;
+
+/// Rendering of outlines, glows and shadows for signed distance fields.
+pub mod effects;
+
+/// Rendering of text with a glyph atlas of distance fields.
+pub mod text;
+
+/// Visualization of field values with colormaps.
+pub mod heatmap;
+
+/// Debug visualization of the vectors to the nearest cells.
+pub mod vectors;
+
+/// A color with straight (not premultiplied) alpha.
+pub type Color = [u8; 4];
+
+/// An image with four 8-bit channels (red, green, blue, alpha) per pixel.
+#[derive(Debug, Clone)]
+pub struct RgbaImage {
+	pub width: u32,
+	pub height: u32,
+	pub data: Vec<u8>,
+}
+
+impl RgbaImage {
+	/// Create a fully transparent image.
+	pub fn new(width: u32, height: u32) -> Self {
+		RgbaImage::filled(width, height, [0, 0, 0, 0])
+	}
+
+	/// Create an image where every pixel has the given color.
+	pub fn filled(width: u32, height: u32, color: Color) -> Self {
+		let data = color.iter()
+			.copied()
+			.cycle()
+			.take(width as usize * height as usize * 4)
+			.collect();
+		RgbaImage {
+			width,
+			height,
+			data,
+		}
+	}
+
+	/// The color of the pixel at the given position.
+	pub fn get_pixel(&self, x: u32, y: u32) -> Color {
+		let offset = self.offset(x, y);
+		[self.data[offset], self.data[offset + 1], self.data[offset + 2], self.data[offset + 3]]
+	}
+
+	pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+		let offset = self.offset(x, y);
+		self.data[offset..offset + 4].copy_from_slice(&color);
+	}
+
+	/// Composite the given color over the pixel at the given position (source over).
+	/// The alpha of the color is multiplied with the coverage (0.0 - 1.0).
+	pub fn blend_pixel(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+		let destination = self.get_pixel(x, y);
+		let src_alpha = color[3] as f32 / 255.0 * coverage.clamp(0.0, 1.0);
+		let dst_alpha = destination[3] as f32 / 255.0;
+		let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+
+		let mut result = [0u8; 4];
+		if out_alpha > 0.0 {
+			for channel in 0..3 {
+				let value = (color[channel] as f32 * src_alpha
+					+ destination[channel] as f32 * dst_alpha * (1.0 - src_alpha)) / out_alpha;
+				result[channel] = value.round() as u8;
+			}
+		}
+		result[3] = (out_alpha * 255.0).round() as u8;
+		self.set_pixel(x, y, result);
+	}
+
+	/// Draw a line (one pixel wide, without anti-aliasing) between two positions in pixel units.
+	/// Parts of the line outside of the image are skipped.
+	pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), color: Color) {
+		let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+		let steps = (dx.abs().max(dy.abs()) * 2.0).ceil().max(1.0) as u32;
+		for step in 0..=steps {
+			let t = step as f32 / steps as f32;
+			let (x, y) = ((from.0 + dx * t).floor(), (from.1 + dy * t).floor());
+			if x >= 0.0 && y >= 0.0 && x < self.width as f32 && y < self.height as f32 {
+				self.set_pixel(x as u32, y as u32, color);
+			}
+		}
+	}
+
+	/// Arrange the images in a grid with the given number of columns, e.g. to create a preview sheet
+	/// of different render settings. Every grid cell has the size of the largest image.
+	/// This fails, if the size of the sheet exceeds `u32::MAX`.
+	pub fn sheet(images: &[RgbaImage], columns: u32, background: Color) -> Result<RgbaImage, String> {
+		let columns = columns.max(1) as usize;
+		let rows = images.len().div_ceil(columns);
+		let cell_width = images.iter().map(|image| image.width).max().unwrap_or(0);
+		let cell_height = images.iter().map(|image| image.height).max().unwrap_or(0);
+
+		let (width, height) = match (u32::try_from(columns.min(images.len())), u32::try_from(rows)) {
+			(Ok(used_columns), Ok(rows)) => (cell_width.checked_mul(used_columns), cell_height.checked_mul(rows)),
+			_ => (None, None),
+		};
+		let (width, height) = match (width, height) {
+			(Some(width), Some(height)) => (width, height),
+			_ => return Err(format!("sheet of {} images with {} columns exceeds the maximum image size", images.len(), columns)),
+		};
+		let mut sheet = RgbaImage::filled(width, height, background);
+		for (index, image) in images.iter().enumerate() {
+			let left = cell_width * (index % columns) as u32;
+			let top = cell_height * (index / columns) as u32;
+			for y in 0..image.height {
+				for x in 0..image.width {
+					sheet.blend_pixel(left + x, top + y, image.get_pixel(x, y), 1.0);
+				}
+			}
+		}
+		Ok(sheet)
+	}
+
+	fn offset(&self, x: u32, y: u32) -> usize {
+		(x as usize + y as usize * self.width as usize) * 4
+	}
+}
+
+/// The size of an image with `scale` x `scale` pixels per cell of the field.
+pub(crate) fn scaled_size(width: u32, height: u32, scale: u32) -> Result<(u32, u32), String> {
+	match (width.checked_mul(scale), height.checked_mul(scale)) {
+		(Some(width), Some(height)) => Ok((width, height)),
+		_ => Err(format!("image of {}x{} cells at a scale of {} exceeds the maximum image size", width, height, scale)),
+	}
+}
+
+/// The size of an image that shows the whole field at the given scale.
+pub(crate) fn output_size(field: &dyn FieldSampler, scale: f32) -> (u32, u32) {
+	let width = ((field.width() as f32 * scale).round() as u32).max(1);
+	let height = ((field.height() as f32 * scale).round() as u32).max(1);
+	(width, height)
+}
+
+/// The position in the field (in cell units) of the center of the given output pixel.
+pub(crate) fn field_position(x: u32, y: u32, scale: f32) -> (f32, f32) {
+	((x as f32 + 0.5) / scale - 0.5, (y as f32 + 0.5) / scale - 0.5)
+}
+
+pub(crate) fn check_scale(scale: f32) {
+	if scale <= 0.0 {
+		panic!("scale must be greater than zero (got {})", scale);
+	}
+}
+
+/// Hermite interpolation between 0.0 (at edge0) and 1.0 (at edge1), like the GLSL function.
+pub(crate) fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+	if edge0 == edge1 {
+		return if x < edge0 { 0.0 } else { 1.0 };
+	}
+	let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+	t * t * (3.0 - 2.0 * t)
+}
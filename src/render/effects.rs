@@ -0,0 +1,140 @@
+use crate::data::sampling::FieldSampler;
+use crate::render::{Color, RgbaImage, smoothstep, check_scale, output_size, field_position};
+
+/// A single effect of the effect renderer.
+/// All widths, radii and offsets are given in cell units of the field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+	/// A solid outline of the given width around the shape.
+	Outline { width: f32, color: Color },
+
+	/// A soft glow around the shape that fades out over the given radius.
+	OuterGlow { radius: f32, color: Color },
+
+	/// A soft glow along the inner edge of the shape that fades out over the given radius.
+	InnerGlow { radius: f32, color: Color },
+
+	/// A copy of the shape, moved by the offset and blurred by the softness, below the shape.
+	DropShadow { offset: (f32, f32), softness: f32, color: Color },
+}
+
+/// Renders a shape with effects (outlines, glows and drop shadows) from a signed distance field.
+/// The field must contain signed distances in cell units like the fields created from a distance field:
+/// negative distances (the foreground layer) are inside the shape and positive distances
+/// (the background layer) are outside of the shape. Inner effects are therefore based on the
+/// foreground distances and outer effects on the background distances.
+///
+/// Independent of the order they were added in, the effects are composited from bottom to top:
+/// drop shadows, outer glows, outlines, the fill of the shape and inner glows.
+pub struct EffectRenderer {
+	scale: f32,
+	fill_color: Color,
+	background_color: Color,
+	effects: Vec<Effect>,
+}
+
+impl EffectRenderer {
+	pub fn new() -> Self {
+		EffectRenderer {
+			scale: 1.0,
+			fill_color: [255, 255, 255, 255],
+			background_color: [0, 0, 0, 0],
+			effects: Vec::new(),
+		}
+	}
+
+	/// The size of an output pixel relative to a cell of the field.
+	pub fn scale(mut self, scale: f32) -> Self {
+		check_scale(scale);
+		self.scale = scale;
+		self
+	}
+
+	pub fn fill_color(mut self, color: Color) -> Self {
+		self.fill_color = color;
+		self
+	}
+
+	pub fn background_color(mut self, color: Color) -> Self {
+		self.background_color = color;
+		self
+	}
+
+	pub fn effect(mut self, effect: Effect) -> Self {
+		self.effects.push(effect);
+		self
+	}
+
+	pub fn outline(self, width: f32, color: Color) -> Self {
+		self.effect(Effect::Outline { width, color })
+	}
+
+	pub fn outer_glow(self, radius: f32, color: Color) -> Self {
+		self.effect(Effect::OuterGlow { radius, color })
+	}
+
+	pub fn inner_glow(self, radius: f32, color: Color) -> Self {
+		self.effect(Effect::InnerGlow { radius, color })
+	}
+
+	pub fn drop_shadow(self, offset: (f32, f32), softness: f32, color: Color) -> Self {
+		self.effect(Effect::DropShadow { offset, softness, color })
+	}
+
+	pub fn render(&self, field: &dyn FieldSampler) -> RgbaImage {
+		let (width, height) = output_size(field, self.scale);
+		let mut image = RgbaImage::filled(width, height, self.background_color);
+
+		// half the size of an output pixel, used to anti-alias the edges
+		let aa = 0.5 / self.scale;
+
+		for y in 0..height {
+			for x in 0..width {
+				let (fx, fy) = field_position(x, y, self.scale);
+				let distance = field.sample(fx, fy);
+				let fill = 1.0 - smoothstep(-aa, aa, distance);
+
+				for layer in 0..=INNER_LAYER {
+					if layer == FILL_LAYER {
+						image.blend_pixel(x, y, self.fill_color, fill);
+					}
+					for effect in self.effects.iter().filter(|effect| stacking_order(effect) == layer) {
+						let (color, coverage) = match *effect {
+							Effect::DropShadow { offset, softness, color } => {
+								let shadow_distance = field.sample(fx - offset.0, fy - offset.1);
+								(color, 1.0 - smoothstep(-softness - aa, softness + aa, shadow_distance))
+							}
+							Effect::OuterGlow { radius, color } =>
+								(color, 1.0 - smoothstep(0.0, radius.max(aa), distance)),
+							Effect::Outline { width, color } =>
+								(color, 1.0 - smoothstep(width - aa, width + aa, distance)),
+							Effect::InnerGlow { radius, color } =>
+								(color, fill * (1.0 - smoothstep(0.0, radius.max(aa), -distance))),
+						};
+						image.blend_pixel(x, y, color, coverage);
+					}
+				}
+			}
+		}
+		image
+	}
+}
+
+impl Default for EffectRenderer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+const FILL_LAYER: u8 = 3;
+const INNER_LAYER: u8 = 4;
+
+/// The layer of an effect (from bottom to top).
+fn stacking_order(effect: &Effect) -> u8 {
+	match effect {
+		Effect::DropShadow { .. } => 0,
+		Effect::OuterGlow { .. } => 1,
+		Effect::Outline { .. } => 2,
+		Effect::InnerGlow { .. } => INNER_LAYER,
+	}
+}
@@ -0,0 +1,167 @@
+use crate::data::sampling::FieldSampler;
+use crate::render::{Color, RgbaImage};
+
+/// A mapping of normalized values (0.0 - 1.0) to colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colormap {
+	/// Perceptually uniform sequential colormap from dark purple to yellow.
+	Viridis,
+
+	/// Perceptually uniform sequential colormap from black over red to light yellow.
+	Inferno,
+
+	/// Diverging colormap from blue over light gray to red, meant for signed fields.
+	/// The center (0.5) of the colormap is mapped to the zero value of the field.
+	BlueRed,
+}
+
+/// Polynomial approximations (6th degree) of the matplotlib colormaps.
+const VIRIDIS: [[f32; 3]; 7] = [
+	[0.277_727_33, 0.005_407_344, 0.334_099_8],
+	[0.105_093_04, 1.404_613_5, 1.384_590_2],
+	[-0.330_861_83, 0.214_847_56, 0.095_095_16],
+	[-4.634_230_6, -5.799_101, -19.332_441],
+	[6.228_27, 14.179_933, 56.690_55],
+	[4.776_385, -13.745_145, -65.353_03],
+	[-5.435_456, 4.645_852_6, 26.312_435],
+];
+
+const INFERNO: [[f32; 3]; 7] = [
+	[0.000_218_940_37, 0.001_651_004_6, -0.019_480_898],
+	[0.106_513_42, 0.563_956_4, 3.932_712_3],
+	[11.602_493, -3.972_854, -15.942_394],
+	[-41.703_995, 17.436_4, 44.354_145],
+	[77.162_94, -33.402_36, -81.807_31],
+	[-71.319_43, 32.626_064, 73.209_52],
+	[25.131_126, -12.242_669, -23.070_325],
+];
+
+const BLUE: [f32; 3] = [0.230, 0.299, 0.754];
+const GRAY: [f32; 3] = [0.865, 0.865, 0.865];
+const RED: [f32; 3] = [0.706, 0.016, 0.150];
+
+impl Colormap {
+	/// The opaque color for the given value, values outside of 0.0 - 1.0 are clamped.
+	pub fn color(&self, value: f32) -> Color {
+		let t = value.clamp(0.0, 1.0);
+		let rgb = match self {
+			Colormap::Viridis => polynomial(&VIRIDIS, t),
+			Colormap::Inferno => polynomial(&INFERNO, t),
+			Colormap::BlueRed => if t < 0.5 {
+				mix(BLUE, GRAY, t * 2.0)
+			} else {
+				mix(GRAY, RED, t * 2.0 - 1.0)
+			},
+		};
+		[to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]), 255]
+	}
+
+	fn is_diverging(&self) -> bool {
+		*self == Colormap::BlueRed
+	}
+}
+
+/// Visualizes the values of a field as a heatmap with one pixel per cell.
+/// Other than the grayscale exports, the values are normalized to the range of the field
+/// (or a given range) before they are mapped to colors, so small distances stay visible.
+pub struct HeatmapRenderer {
+	colormap: Colormap,
+	range: Option<(f32, f32)>,
+	isoline_spacing: Option<f32>,
+	isoline_color: Color,
+}
+
+impl HeatmapRenderer {
+	pub fn new(colormap: Colormap) -> Self {
+		HeatmapRenderer {
+			colormap,
+			range: None,
+			isoline_spacing: None,
+			isoline_color: [255, 255, 255, 255],
+		}
+	}
+
+	/// The values that are mapped to the start and the end of the colormap.
+	/// By default this is the range of the field, diverging colormaps use
+	/// a range that is symmetric around zero.
+	pub fn range(mut self, min: f32, max: f32) -> Self {
+		self.range = Some((min, max));
+		self
+	}
+
+	/// Draw an isoline at every multiple of the given spacing (in field values, i.e. cells for distances).
+	pub fn isolines(mut self, spacing: f32) -> Self {
+		if spacing <= 0.0 {
+			panic!("isoline spacing must be greater than zero (got {})", spacing);
+		}
+		self.isoline_spacing = Some(spacing);
+		self
+	}
+
+	pub fn isoline_color(mut self, color: Color) -> Self {
+		self.isoline_color = color;
+		self
+	}
+
+	pub fn render(&self, field: &dyn FieldSampler) -> RgbaImage {
+		let (min, max) = self.range.unwrap_or_else(|| self.field_range(field));
+		let extent = if max > min { max - min } else { 1.0 };
+
+		let mut image = RgbaImage::new(field.width(), field.height());
+		for y in 0..field.height() {
+			for x in 0..field.width() {
+				let value = field.value_at(x, y);
+				image.set_pixel(x, y, self.colormap.color((value - min) / extent));
+				if self.is_on_isoline(field, x, y) {
+					image.blend_pixel(x, y, self.isoline_color, 1.0);
+				}
+			}
+		}
+		image
+	}
+
+	fn field_range(&self, field: &dyn FieldSampler) -> (f32, f32) {
+		let (mut min, mut max) = (f32::MAX, f32::MIN);
+		for y in 0..field.height() {
+			for x in 0..field.width() {
+				let value = field.value_at(x, y);
+				min = min.min(value);
+				max = max.max(value);
+			}
+		}
+		if self.colormap.is_diverging() {
+			let limit = min.abs().max(max.abs());
+			(-limit, limit)
+		} else {
+			(min, max)
+		}
+	}
+
+	/// A cell is on an isoline, if an isoline lies between the cell and its right or bottom neighbour.
+	fn is_on_isoline(&self, field: &dyn FieldSampler, x: u32, y: u32) -> bool {
+		let spacing = match self.isoline_spacing {
+			Some(spacing) => spacing,
+			None => return false,
+		};
+		let band = |value: f32| (value / spacing).floor();
+		let current = band(field.value_at(x, y));
+		(x + 1 < field.width() && band(field.value_at(x + 1, y)) != current)
+			|| (y + 1 < field.height() && band(field.value_at(x, y + 1)) != current)
+	}
+}
+
+fn polynomial(coefficients: &[[f32; 3]; 7], t: f32) -> [f32; 3] {
+	let mut result = [0f32; 3];
+	for (channel, value) in result.iter_mut().enumerate() {
+		*value = coefficients.iter().rev().fold(0.0, |sum, coefficient| sum * t + coefficient[channel]);
+	}
+	result
+}
+
+fn mix(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+	[a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+}
+
+fn to_u8(value: f32) -> u8 {
+	(value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
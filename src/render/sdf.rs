@@ -0,0 +1,105 @@
+use crate::data::sampling::FieldSampler;
+use crate::data::scalar::ScalarField;
+use crate::render::{Color, RgbaImage, smoothstep, check_scale, output_size, field_position};
+use crate::result::DistanceTransformationResult;
+
+/// Renders the shape encoded in a distance field, like a fragment shader for SDF textures would do.
+/// The field is sampled with bilinear interpolation at the center of every output pixel,
+/// values equal or above the threshold are inside the shape.
+/// The edge of the shape is anti-aliased with a smoothstep over the smoothing width.
+pub struct SdfRenderer {
+	scale: f32,
+	threshold: f32,
+	smoothing: Option<f32>,
+	inverted: bool,
+	fill_color: Color,
+	background_color: Color,
+}
+
+impl SdfRenderer {
+	pub fn new() -> Self {
+		SdfRenderer {
+			scale: 1.0,
+			threshold: 0.5,
+			smoothing: None,
+			inverted: false,
+			fill_color: [255, 255, 255, 255],
+			background_color: [0, 0, 0, 0],
+		}
+	}
+
+	/// The size of an output pixel relative to a cell of the field.
+	/// A scale of 2.0 renders an image with twice the width and height of the field.
+	pub fn scale(mut self, scale: f32) -> Self {
+		check_scale(scale);
+		self.scale = scale;
+		self
+	}
+
+	/// The value of the field at the edge of the shape.
+	/// The default of 0.5 matches the inner distances of a distance transformation,
+	/// where the inside has distances of one or more and the outside has zero distances.
+	pub fn threshold(mut self, threshold: f32) -> Self {
+		self.threshold = threshold;
+		self
+	}
+
+	/// The half width of the anti-aliased edge, given in field values.
+	/// By default the width is derived from the gradient of the field,
+	/// so the edge is about one output pixel wide.
+	pub fn smoothing(mut self, smoothing: f32) -> Self {
+		self.smoothing = Some(smoothing);
+		self
+	}
+
+	/// Regard values below the threshold as inside of the shape.
+	/// This is needed for signed fields, where the inside has negative values.
+	pub fn inverted(mut self) -> Self {
+		self.inverted = true;
+		self
+	}
+
+	pub fn fill_color(mut self, color: Color) -> Self {
+		self.fill_color = color;
+		self
+	}
+
+	pub fn background_color(mut self, color: Color) -> Self {
+		self.background_color = color;
+		self
+	}
+
+	/// Render the first channel of an encoded distance transformation.
+	pub fn render_result(&self, result: &DistanceTransformationResult) -> RgbaImage {
+		self.render(&ScalarField::from(result))
+	}
+
+	pub fn render(&self, field: &dyn FieldSampler) -> RgbaImage {
+		let (width, height) = output_size(field, self.scale);
+		let mut image = RgbaImage::filled(width, height, self.background_color);
+		for y in 0..height {
+			for x in 0..width {
+				let (fx, fy) = field_position(x, y, self.scale);
+				image.blend_pixel(x, y, self.fill_color, self.coverage(field, fx, fy));
+			}
+		}
+		image
+	}
+
+	/// The anti-aliased coverage (0.0 - 1.0) of the shape at the given position.
+	fn coverage(&self, field: &dyn FieldSampler, x: f32, y: f32) -> f32 {
+		let value = field.sample(x, y);
+		let inside = if self.inverted { self.threshold - value } else { value - self.threshold };
+		let smoothing = self.smoothing.unwrap_or_else(|| {
+			let (dx, dy) = field.gradient(x, y);
+			0.5 * (dx * dx + dy * dy).sqrt() / self.scale
+		});
+		smoothstep(-smoothing, smoothing, inside)
+	}
+}
+
+impl Default for SdfRenderer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use crate::data::sampling::FieldSampler;
+use crate::data::scalar::ScalarField;
+use crate::input::font::FontInput;
+use crate::processor::SourceProcessor;
+use crate::render::{Color, RgbaImage, smoothstep};
+
+/// The signed distance fields of the glyphs of a font, packed into a single field.
+/// All measures are given in atlas pixels, i.e. pixels for the size the glyphs were generated with.
+pub struct GlyphAtlas {
+	pub field: ScalarField,
+	size: f32,
+	ascender: f32,
+	descender: f32,
+	glyphs: HashMap<char, AtlasGlyph>,
+	kerning: HashMap<(char, char), f32>,
+}
+
+/// The placement of a single glyph in the atlas.
+#[derive(Debug, Clone, Copy)]
+struct AtlasGlyph {
+	x: u32,
+	y: u32,
+	width: u32,
+	height: u32,
+	left: f32,
+	top: f32,
+	advance: f32,
+}
+
+impl GlyphAtlas {
+	/// Generate the atlas for the given characters of the font.
+	/// The size is the height of the em square in atlas pixels, the padding is the number of
+	/// pixels around each glyph and limits the distances that can be sampled outside of the glyphs.
+	pub fn new(font: &FontInput, characters: &str, size: f32, padding: u32, processor: &dyn SourceProcessor) -> Self {
+		let mut characters: Vec<char> = characters.chars().collect();
+		characters.sort_unstable();
+		characters.dedup();
+
+		// generate the distance fields of all glyphs
+		let mut fields = Vec::new();
+		let mut glyphs = HashMap::new();
+		for character in characters.iter() {
+			let glyph = font.glyph(*character, size, padding);
+			let mut entry = AtlasGlyph { x: 0, y: 0, width: 0, height: 0, left: glyph.left, top: glyph.top, advance: glyph.advance };
+			if let Some(input) = glyph.field {
+				let field = ScalarField::from(&processor.process(&input));
+				entry.width = field.width;
+				entry.height = field.height;
+				fields.push((*character, field));
+			}
+			glyphs.insert(*character, entry);
+		}
+
+		// pack the fields in rows (the tallest glyphs first)
+		fields.sort_by(|a, b| b.1.height.cmp(&a.1.height).then(a.0.cmp(&b.0)));
+		let area: f32 = fields.iter().map(|(_, field)| field.width as f32 * field.height as f32).sum();
+		let max_width = fields.iter().map(|(_, field)| field.width).max().unwrap_or(0);
+		let row_width = (area.sqrt().ceil() as u32).max(max_width);
+
+		let (mut x, mut y, mut row_height, mut width) = (0u32, 0u32, 0u32, 0u32);
+		for (character, field) in fields.iter() {
+			if x + field.width > row_width {
+				x = 0;
+				y += row_height;
+				row_height = 0;
+			}
+			let entry = glyphs.get_mut(character).unwrap();
+			entry.x = x;
+			entry.y = y;
+			x += field.width;
+			width = width.max(x);
+			row_height = row_height.max(field.height);
+		}
+		let height = y + row_height;
+
+		// unused parts of the atlas are far outside of every glyph
+		let mut data = vec![size; width as usize * height as usize];
+		for (character, field) in fields.iter() {
+			let entry = &glyphs[character];
+			for row in 0..field.height as usize {
+				let source = row * field.width as usize;
+				let target = entry.x as usize + (entry.y as usize + row) * width as usize;
+				data[target..target + field.width as usize].copy_from_slice(&field.data[source..source + field.width as usize]);
+			}
+		}
+
+		let mut kerning = HashMap::new();
+		for left in characters.iter() {
+			for right in characters.iter() {
+				let value = font.kerning(*left, *right, size);
+				if value != 0.0 {
+					kerning.insert((*left, *right), value);
+				}
+			}
+		}
+
+		GlyphAtlas {
+			field: ScalarField::new(data, width, height),
+			size,
+			ascender: font.ascender(size),
+			descender: font.descender(size),
+			glyphs,
+			kerning,
+		}
+	}
+
+	/// The height of the em square in atlas pixels.
+	pub fn size(&self) -> f32 {
+		self.size
+	}
+
+	/// The kerning between two characters in atlas pixels.
+	pub fn kerning(&self, left: char, right: char) -> f32 {
+		*self.kerning.get(&(left, right)).unwrap_or(&0.0)
+	}
+}
+
+/// A view on the part of the atlas that contains a single glyph.
+/// Positions outside of the glyph are clamped to its border, so neighbouring glyphs never bleed in.
+struct GlyphView<'a> {
+	atlas: &'a ScalarField,
+	glyph: &'a AtlasGlyph,
+}
+
+impl FieldSampler for GlyphView<'_> {
+	fn width(&self) -> u32 {
+		self.glyph.width
+	}
+
+	fn height(&self) -> u32 {
+		self.glyph.height
+	}
+
+	fn value_at(&self, x: u32, y: u32) -> f32 {
+		self.atlas.get(self.glyph.x + x, self.glyph.y + y)
+	}
+}
+
+/// A glyph placed on the line, the position is the top-left corner in atlas pixels.
+struct PlacedGlyph<'a> {
+	x: f32,
+	y: f32,
+	glyph: &'a AtlasGlyph,
+}
+
+/// Renders a single line of text from a glyph atlas.
+/// The glyphs are placed along the baseline with the advances and kerning of the font,
+/// the line can be rendered at any size and rotation.
+pub struct TextRenderer {
+	size: f32,
+	rotation: f32,
+	margin: f32,
+	fill_color: Color,
+	background_color: Color,
+}
+
+impl TextRenderer {
+	pub fn new() -> Self {
+		TextRenderer {
+			size: 32.0,
+			rotation: 0.0,
+			margin: 2.0,
+			fill_color: [255, 255, 255, 255],
+			background_color: [0, 0, 0, 0],
+		}
+	}
+
+	/// The height of the em square in output pixels.
+	pub fn size(mut self, size: f32) -> Self {
+		if size <= 0.0 {
+			panic!("size must be greater than zero (got {})", size);
+		}
+		self.size = size;
+		self
+	}
+
+	/// The counterclockwise rotation of the line in degrees.
+	pub fn rotation(mut self, degrees: f32) -> Self {
+		self.rotation = degrees.to_radians();
+		self
+	}
+
+	/// The empty space (in output pixels) around the line.
+	pub fn margin(mut self, margin: f32) -> Self {
+		self.margin = margin;
+		self
+	}
+
+	pub fn fill_color(mut self, color: Color) -> Self {
+		self.fill_color = color;
+		self
+	}
+
+	pub fn background_color(mut self, color: Color) -> Self {
+		self.background_color = color;
+		self
+	}
+
+	/// Render the text into an image that contains the whole (rotated) line.
+	/// Characters that are not part of the atlas are skipped.
+	pub fn render(&self, atlas: &GlyphAtlas, text: &str) -> RgbaImage {
+		let placed = layout(atlas, text);
+
+		// the bounds of the line in atlas pixels (the baseline is at y = 0)
+		let mut bounds = (0f32, -atlas.ascender, 0f32, -atlas.descender);
+		let mut pen = 0f32;
+		for glyph in placed.iter() {
+			bounds.0 = bounds.0.min(glyph.x);
+			bounds.1 = bounds.1.min(glyph.y);
+			bounds.2 = bounds.2.max(glyph.x + glyph.glyph.width as f32);
+			bounds.3 = bounds.3.max(glyph.y + glyph.glyph.height as f32);
+			pen = glyph.x - glyph.glyph.left + glyph.glyph.advance;
+		}
+		bounds.2 = bounds.2.max(pen);
+
+		// the bounds of the rotated and scaled line in output pixels
+		let scale = self.size / atlas.size;
+		let (sin, cos) = self.rotation.sin_cos();
+		let rotate = |x: f32, y: f32| ((x * cos + y * sin) * scale, (-x * sin + y * cos) * scale);
+		let corners = [rotate(bounds.0, bounds.1), rotate(bounds.2, bounds.1), rotate(bounds.0, bounds.3), rotate(bounds.2, bounds.3)];
+		let min_x = corners.iter().map(|corner| corner.0).fold(f32::MAX, f32::min) - self.margin;
+		let min_y = corners.iter().map(|corner| corner.1).fold(f32::MAX, f32::min) - self.margin;
+		let max_x = corners.iter().map(|corner| corner.0).fold(f32::MIN, f32::max) + self.margin;
+		let max_y = corners.iter().map(|corner| corner.1).fold(f32::MIN, f32::max) + self.margin;
+		let width = ((max_x - min_x).ceil() as u32).max(1);
+		let height = ((max_y - min_y).ceil() as u32).max(1);
+
+		// half the size of an output pixel in atlas pixels
+		let aa = 0.5 / scale;
+
+		let mut image = RgbaImage::filled(width, height, self.background_color);
+		for y in 0..height {
+			for x in 0..width {
+				// the position of the pixel center on the line
+				let ox = (min_x + x as f32 + 0.5) / scale;
+				let oy = (min_y + y as f32 + 0.5) / scale;
+				let lx = ox * cos - oy * sin;
+				let ly = ox * sin + oy * cos;
+
+				let distance = placed.iter()
+					.filter(|glyph| lx >= glyph.x && ly >= glyph.y
+						&& lx < glyph.x + glyph.glyph.width as f32 && ly < glyph.y + glyph.glyph.height as f32)
+					.map(|glyph| GlyphView { atlas: &atlas.field, glyph: glyph.glyph }.sample(lx - glyph.x - 0.5, ly - glyph.y - 0.5))
+					.fold(f32::MAX, f32::min);
+				if distance < aa {
+					image.blend_pixel(x, y, self.fill_color, 1.0 - smoothstep(-aa, aa, distance));
+				}
+			}
+		}
+		image
+	}
+}
+
+impl Default for TextRenderer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Places the glyphs of the text along the baseline.
+fn layout<'a>(atlas: &'a GlyphAtlas, text: &str) -> Vec<PlacedGlyph<'a>> {
+	let mut placed = Vec::new();
+	let mut pen = 0f32;
+	let mut previous: Option<char> = None;
+	for character in text.chars() {
+		let glyph = match atlas.glyphs.get(&character) {
+			Some(glyph) => glyph,
+			None => continue,
+		};
+		if let Some(previous) = previous {
+			pen += atlas.kerning(previous, character);
+		}
+		placed.push(PlacedGlyph { x: pen + glyph.left, y: glyph.top, glyph });
+		pen += glyph.advance;
+		previous = Some(character);
+	}
+	placed
+}
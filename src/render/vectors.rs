@@ -0,0 +1,207 @@
+use crate::data::{Cell, CellLayer, DistanceField};
+use crate::distance::{DistanceLayer, DistanceType};
+use crate::distance::cartesian::CartesianDistance;
+use crate::render::{Color, RgbaImage, scaled_size};
+
+/// The way the vectors to the nearest cells are visualized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorMode {
+	/// Arrows from the sampled cells to their nearest cells, drawn over the input mask.
+	Arrows,
+
+	/// Every cell is colored by its vector: the direction is the hue and the length is the value.
+	Hsv,
+}
+
+/// Debug renderer for the two-dimensional distance types (`CartesianDistance` and `NearestCellPosition`).
+/// It shows the vector from every cell to its nearest cell of the opposite layer,
+/// which makes errors of the distance processing visible.
+/// Every cell is rendered as a square of `scale` x `scale` pixels.
+pub struct VectorFieldRenderer {
+	mode: VectorMode,
+	distance_type: DistanceType,
+	layer: DistanceLayer,
+	scale: u32,
+	spacing: u32,
+	foreground_color: Color,
+	background_color: Color,
+	arrow_color: Color,
+}
+
+impl VectorFieldRenderer {
+	/// Only `CartesianDistance` and `NearestCellPosition` are valid distance types.
+	pub fn new(mode: VectorMode, distance_type: DistanceType) -> Self {
+		match distance_type {
+			DistanceType::CartesianDistance | DistanceType::NearestCellPosition => {}
+			_ => panic!("distance type {} has no vectors", distance_type.human_readable_name()),
+		}
+		VectorFieldRenderer {
+			mode,
+			distance_type,
+			layer: DistanceLayer::Combined,
+			scale: 8,
+			spacing: 1,
+			foreground_color: [96, 96, 96, 255],
+			background_color: [32, 32, 32, 255],
+			arrow_color: [255, 200, 0, 255],
+		}
+	}
+
+	/// Only show the vectors of the cells of the given layer.
+	pub fn layer(mut self, layer: DistanceLayer) -> Self {
+		self.layer = layer;
+		self
+	}
+
+	/// The size of a cell in pixels.
+	pub fn scale(mut self, scale: u32) -> Self {
+		self.scale = scale.max(1);
+		self
+	}
+
+	/// Only draw an arrow for every n-th cell (in both directions).
+	pub fn spacing(mut self, spacing: u32) -> Self {
+		self.spacing = spacing.max(1);
+		self
+	}
+
+	/// The colors of the input mask (foreground and background cells).
+	pub fn mask_colors(mut self, foreground: Color, background: Color) -> Self {
+		self.foreground_color = foreground;
+		self.background_color = background;
+		self
+	}
+
+	pub fn arrow_color(mut self, color: Color) -> Self {
+		self.arrow_color = color;
+		self
+	}
+
+	/// Render the vectors of the field.
+	/// This fails, if the size of the image (the size of the field times the scale) exceeds `u32::MAX`.
+	pub fn render(&self, df: &DistanceField) -> Result<RgbaImage, String> {
+		let (width, height) = scaled_size(df.width, df.height, self.scale)?;
+		let image = RgbaImage::new(width, height);
+		Ok(match self.mode {
+			VectorMode::Arrows => self.render_arrows(df, image),
+			VectorMode::Hsv => self.render_hsv(df, image),
+		})
+	}
+
+	fn render_arrows(&self, df: &DistanceField, mut image: RgbaImage) -> RgbaImage {
+		let scale = self.scale as f32;
+		for (index, cell) in df.cells().enumerate() {
+			let color = match cell.layer {
+				CellLayer::Foreground => self.foreground_color,
+				CellLayer::Background => self.background_color,
+			};
+			let (x, y) = cell_position(df, index);
+			self.fill_cell(&mut image, x, y, color);
+		}
+
+		for (index, cell) in df.cells().enumerate() {
+			let (x, y) = cell_position(df, index);
+			if x % self.spacing != 0 || y % self.spacing != 0 || !self.is_selected(&cell) {
+				continue;
+			}
+			let (dx, dy) = match self.vector(&cell) {
+				Some(vector) => vector,
+				None => continue,
+			};
+			let from = ((x as f32 + 0.5) * scale, (y as f32 + 0.5) * scale);
+			let to = (from.0 + dx * scale, from.1 + dy * scale);
+			image.draw_line(from, to, self.arrow_color);
+
+			// the head of the arrow
+			let length = (dx * dx + dy * dy).sqrt() * scale;
+			let head = (scale * 0.5).min(length * 0.5);
+			let angle = dy.atan2(dx);
+			for side in [-0.5f32, 0.5].iter() {
+				let (sin, cos) = (angle + std::f32::consts::PI + side).sin_cos();
+				image.draw_line(to, (to.0 + cos * head, to.1 + sin * head), self.arrow_color);
+			}
+		}
+		image
+	}
+
+	fn render_hsv(&self, df: &DistanceField, mut image: RgbaImage) -> RgbaImage {
+		let vectors: Vec<Option<(f32, f32)>> = df.cells()
+			.map(|cell| if self.is_selected(&cell) { self.vector(&cell) } else { None })
+			.collect();
+		let max_length = vectors.iter()
+			.flatten()
+			.map(|(dx, dy)| (dx * dx + dy * dy).sqrt())
+			.fold(0f32, f32::max);
+
+		for (index, vector) in vectors.iter().enumerate() {
+			let color = match vector {
+				Some((dx, dy)) => {
+					let hue = dy.atan2(*dx).to_degrees().rem_euclid(360.0);
+					hsv_to_rgb(hue, 1.0, (dx * dx + dy * dy).sqrt() / max_length)
+				}
+				None => [0, 0, 0, 255],
+			};
+			let (x, y) = cell_position(df, index);
+			self.fill_cell(&mut image, x, y, color);
+		}
+		image
+	}
+
+	fn is_selected(&self, cell: &Cell) -> bool {
+		match self.layer {
+			DistanceLayer::Foreground => cell.layer == CellLayer::Foreground,
+			DistanceLayer::Background => cell.layer == CellLayer::Background,
+			DistanceLayer::Combined => true,
+		}
+	}
+
+	/// The vector from the cell to its nearest cell, this is `None` for cells without (or at) their nearest cell.
+	fn vector(&self, cell: &Cell) -> Option<(f32, f32)> {
+		cell.nearest_cell_position?;
+		let (dx, dy) = match self.distance_type {
+			DistanceType::CartesianDistance => {
+				let (dx, dy) = CartesianDistance::calculate(cell);
+				(dx as f32, dy as f32)
+			}
+			_ => {
+				let (dx, dy) = cell.nearest_vector()?;
+				(dx as f32, dy as f32)
+			}
+		};
+		if dx == 0.0 && dy == 0.0 {
+			None
+		} else {
+			Some((dx, dy))
+		}
+	}
+
+	fn fill_cell(&self, image: &mut RgbaImage, x: u32, y: u32, color: Color) {
+		for py in y * self.scale..(y + 1) * self.scale {
+			for px in x * self.scale..(x + 1) * self.scale {
+				image.set_pixel(px, py, color);
+			}
+		}
+	}
+}
+
+fn cell_position(df: &DistanceField, index: usize) -> (u32, u32) {
+	((index % df.width as usize) as u32, (index / df.width as usize) as u32)
+}
+
+/// Converts a color with hue (0 - 360), saturation and value (0.0 - 1.0) into an opaque RGBA color.
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Color {
+	let chroma = value * saturation;
+	let sector = hue / 60.0;
+	let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+	let (r, g, b) = match sector as u8 {
+		0 => (chroma, x, 0.0),
+		1 => (x, chroma, 0.0),
+		2 => (0.0, chroma, x),
+		3 => (0.0, x, chroma),
+		4 => (x, 0.0, chroma),
+		_ => (chroma, 0.0, x),
+	};
+	let m = value - chroma;
+	let to_u8 = |channel: f32| ((channel + m) * 255.0).round() as u8;
+	[to_u8(r), to_u8(g), to_u8(b), 255]
+}
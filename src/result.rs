@@ -1,6 +1,8 @@
+use crate::data::input::Spacing;
 use crate::data::transformation::{TransformationResult};
 use crate::data::serialize::ByteSerializer;
 
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ChannelDataType {
 	UnsignedInt,
 	SignedInt,
@@ -33,9 +35,12 @@ pub enum BitDepth {
 	SixtyFour,
 }
 
+/// The encoded channels of a distance transformation.
+/// The spacing is the physical size of the cells, `PngOutput` writes it as pHYs chunk.
 pub struct DistanceTransformationResult {
-	pub width: u16,
-	pub height: u16,
+	pub width: u32,
+	pub height: u32,
+	pub spacing: Spacing,
 	pub num_channels: u8,
 	pub data_type: ChannelDataType,
 	pub bit_depth: ChannelBitDepth,
@@ -46,26 +51,30 @@ pub struct DistanceTransformationResult {
 // TODO: can we make this generic over T ?
 impl From<TransformationResult<f64>> for DistanceTransformationResult {
 	fn from(result: TransformationResult<f64>) -> Self {
-		let width: u16;
-		let height: u16;
+		let width: u32;
+		let height: u32;
+		let spacing: Spacing;
 		let num_channels: u8;
 		let data: Vec<u8>;
 		match result {
 			TransformationResult::OneDimensional(trans_data) => {
 				width = trans_data.width;
 				height = trans_data.height;
+				spacing = trans_data.spacing;
 				num_channels = 1;
 				data = trans_data.data.serialize_to_bytes();
 			}
 			TransformationResult::TwoDimensional(trans_data) => {
 				width = trans_data.width;
 				height = trans_data.height;
+				spacing = trans_data.spacing;
 				num_channels = 2;
 				data = trans_data.data.serialize_to_bytes();
 			}
 			TransformationResult::ThreeDimensional(trans_data) => {
 				width = trans_data.width;
 				height = trans_data.height;
+				spacing = trans_data.spacing;
 				num_channels = 3;
 				data = trans_data.data.serialize_to_bytes();
 			}
@@ -74,6 +83,7 @@ impl From<TransformationResult<f64>> for DistanceTransformationResult {
 		DistanceTransformationResult {
 			width,
 			height,
+			spacing,
 			data,
 			data_type: ChannelDataType::Float,
 			bit_depth: ChannelBitDepth::SixtyFour,
@@ -33,6 +33,18 @@ pub fn u32_to_u16_clamped(value: u32) -> u16 {
 	}
 }
 
+pub fn u64_to_u32_clamped(value: u64) -> u32 {
+	if value > u32::MAX as u64 {
+		u32::MAX
+	} else {
+		value as u32
+	}
+}
+
+pub fn i64_to_i32_clamped(value: i64) -> i32 {
+	value.max(i32::MIN as i64).min(i32::MAX as i64) as i32
+}
+
 pub fn f32_to_u8_clamped(value: f32) -> u8 {
 	if value > 255f32 {
 		255u8
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::input::image::PngInput;
+	use rs_sdf::operation::bleed::AlphaBleed;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::render::RgbaImage;
+
+	// helper method to get a 5x1 image with a red and a half transparent green pixel on the left
+	fn get_image_5_1() -> RgbaImage {
+		let mut image = RgbaImage::new(5, 1);
+		image.set_pixel(0, 0, [255, 0, 0, 255]);
+		image.set_pixel(1, 0, [0, 255, 0, 128]);
+		image
+	}
+
+	#[test]
+	fn fills_transparent_pixels_with_nearest_color() {
+		let result = AlphaBleed::new(EightSideSweepProcessor {}).apply(&get_image_5_1());
+
+		assert_eq!(result.get_pixel(0, 0), [255, 0, 0, 255]);
+		assert_eq!(result.get_pixel(1, 0), [0, 255, 0, 128]);
+		assert_eq!(result.get_pixel(2, 0), [0, 255, 0, 0]);
+		assert_eq!(result.get_pixel(4, 0), [0, 255, 0, 0]);
+	}
+
+	#[test]
+	fn respects_max_distance() {
+		let result = AlphaBleed::new(EightSideSweepProcessor {}).max_distance(2.0).apply(&get_image_5_1());
+
+		assert_eq!(result.get_pixel(3, 0), [0, 255, 0, 0]);
+		assert_eq!(result.get_pixel(4, 0), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn keeps_fully_transparent_image() {
+		let image = RgbaImage::filled(3, 2, [10, 20, 30, 0]);
+		let result = AlphaBleed::new(EightSideSweepProcessor {}).apply(&image);
+		assert_eq!(result.data, image.data);
+	}
+
+	#[test]
+	fn reads_rgba_png() {
+		let image = PngInput::new("tests/test_assets/test_rgba_2x2_checkerboard.png").rgba_image().unwrap();
+		assert_eq!((image.width, image.height), (2, 2));
+		assert_eq!(image.data.len(), 16);
+
+		assert!(PngInput::new("tests/test_assets/test_rgb_1x1_black.png").rgba_image().is_err());
+	}
+}
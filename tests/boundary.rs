@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::{DistanceField, NO_CELL_INDEX};
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::distance::cartesian::CartesianDistance;
+	use rs_sdf::distance::euclid::EuclideanDistance;
+	use rs_sdf::distance::nearest_cell::{NearestCellIndex, NearestCellIndexOffset, NearestCellPosition};
+	use rs_sdf::distance::OneDimensionalDistanceCalculation;
+	use rs_sdf::operation::bleed::AlphaBleed;
+	use rs_sdf::processor::boundary::{Boundary, BoundaryProcessor};
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::RgbaImage;
+
+	fn distances(df: &DistanceField) -> Vec<f64> {
+		df.cells().map(|cell| EuclideanDistance::calculate(&cell)).collect()
+	}
+
+	#[test]
+	fn ignores_the_outside_by_default() {
+		let source = InputField::from(BoolInputData::new(vec![true, false, false, false, false, false], 6, 1));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {}).process(&source);
+		assert_eq!(distances(&df), vec![1.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+	}
+
+	#[test]
+	fn wraps_around_horizontally() {
+		let source = InputField::from(BoolInputData::new(vec![true, false, false, false, false, false], 6, 1));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.horizontal(Boundary::Periodic)
+			.process(&source);
+
+		assert_eq!(distances(&df), vec![1.0, 1.0, 2.0, 3.0, 2.0, 1.0]);
+
+		// the nearest cell of the last cell is the first cell (across the border)
+		let last = &df.cell(5);
+		assert_eq!(last.index, 5);
+		assert_eq!(last.nearest_cell_position.unwrap().index, 0);
+	}
+
+	#[test]
+	fn matches_brute_force_distances_on_a_torus() {
+		let (w, h) = (12usize, 9usize);
+		let dots = [(1usize, 1usize), (10, 7), (6, 4)];
+		let mut data = vec![false; w * h];
+		dots.iter().for_each(|(x, y)| data[x + y * w] = true);
+		let source = InputField::from(BoolInputData::new(data, w as u32, h as u32));
+
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.boundary(Boundary::Periodic)
+			.process(&source);
+
+		for (index, cell) in df.cells().enumerate() {
+			let (x, y) = (index % w, index / w);
+			if dots.contains(&(x, y)) {
+				continue;
+			}
+			let expected = dots.iter()
+				.map(|(dx, dy)| {
+					let horizontal = (x as i32 - *dx as i32).abs().min(w as i32 - (x as i32 - *dx as i32).abs());
+					let vertical = (y as i32 - *dy as i32).abs().min(h as i32 - (y as i32 - *dy as i32).abs());
+					((horizontal * horizontal + vertical * vertical) as f64).sqrt()
+				})
+				.fold(f64::MAX, f64::min);
+			assert_eq!(EuclideanDistance::calculate(&cell), expected, "cell ({}, {})", x, y);
+		}
+	}
+
+	#[test]
+	fn treats_outside_as_background() {
+		let source = InputField::from(BoolInputData::new(vec![true, true, true, true, false, false], 6, 1));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.horizontal(Boundary::Background)
+			.process(&source);
+
+		// the first cell is next to the background beyond the border
+		assert_eq!(distances(&df), vec![1.0, 2.0, 2.0, 1.0, 1.0, 2.0]);
+
+		// the nearest cell of the first cell is a virtual cell, that has no index
+		let first = df.cell(0);
+		assert_eq!(first.nearest_cell_position.unwrap().index, NO_CELL_INDEX);
+		assert_eq!(first.border_offset, (-1, 0));
+		assert_eq!(NearestCellIndex::calculate_u64(&first), NO_CELL_INDEX);
+		assert_eq!(NearestCellIndexOffset::calculate_i64(&first), 0);
+		let index: u16 = NearestCellIndex::calculate_legacy(&first);
+		assert_eq!(index, u16::MAX);
+
+		// the nearest cells of the other cells are cells of the field
+		assert_eq!(df.cell(3).nearest_cell_position.unwrap().index, 4);
+
+		// beyond the top and bottom border is background too
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.boundary(Boundary::Background)
+			.process(&source);
+		assert_eq!(distances(&df), vec![1.0, 1.0, 1.0, 1.0, 1.0, 2.0]);
+	}
+
+	#[test]
+	fn treats_outside_as_foreground() {
+		let source = InputField::from(BoolInputData::new(vec![
+			false, false, false,
+			false, false, false,
+			false, false, false,
+		], 3, 3));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.boundary(Boundary::Foreground)
+			.process(&source);
+
+		assert_eq!(distances(&df), vec![1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 1.0]);
+	}
+
+	#[test]
+	fn combines_boundaries_of_both_axes() {
+		let source = InputField::from(BoolInputData::new(vec![
+			true, false, false, false,
+			true, true, true, true,
+		], 4, 2));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.horizontal(Boundary::Periodic)
+			.vertical(Boundary::Background)
+			.process(&source);
+
+		// the background cell on the right is a neighbour of the foreground cell on the left
+		assert_eq!(distances(&df), vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+		assert_eq!(df.cell(3).nearest_cell_position.unwrap().index, 0);
+	}
+
+	#[test]
+	fn keeps_the_coordinates_of_the_field() {
+		let mut data = vec![false; 64];
+		data[0] = true;
+		let source = InputField::from(BoolInputData::new(data, 8, 8));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.boundary(Boundary::Periodic)
+			.process(&source);
+
+		let first = df.cell(0);
+		assert_eq!((first.x, first.y), (0, 0));
+
+		// the nearest cell of the last cell is the first cell (across both borders)
+		let last = df.cell(63);
+		assert_eq!((last.x, last.y), (7, 7));
+		assert_eq!(NearestCellPosition::calculate(&last), (0, 0));
+		assert_eq!(last.nearest_cell_position.unwrap().index, 0);
+		assert_eq!(last.border_offset, (8, 8));
+		assert_eq!(CartesianDistance::calculate(&last), (1, 1));
+		assert_eq!(EuclideanDistance::calculate(&last), 2f64.sqrt());
+	}
+
+	#[test]
+	fn bleeds_alpha_across_periodic_borders() {
+		let mut image = RgbaImage::new(8, 8);
+		image.set_pixel(0, 0, [255, 0, 0, 255]);
+		image.set_pixel(4, 4, [0, 255, 0, 255]);
+		let result = AlphaBleed::new(BoundaryProcessor::new(EightSideSweepProcessor {}).boundary(Boundary::Periodic))
+			.apply(&image);
+
+		assert_eq!(result.get_pixel(7, 7), [255, 0, 0, 0]);
+		assert_eq!(result.get_pixel(7, 1), [255, 0, 0, 0]);
+		assert_eq!(result.get_pixel(3, 3), [0, 255, 0, 0]);
+	}
+
+	#[test]
+	fn does_not_bleed_virtual_cells_beyond_a_constant_border() {
+		let mut image = RgbaImage::filled(4, 1, [10, 20, 30, 0]);
+		image.set_pixel(3, 0, [255, 0, 0, 255]);
+		let result = AlphaBleed::new(BoundaryProcessor::new(EightSideSweepProcessor {}).horizontal(Boundary::Foreground))
+			.apply(&image);
+
+		// the first pixel is next to the virtual foreground beyond the left border
+		assert_eq!(result.get_pixel(0, 0), [10, 20, 30, 0]);
+		assert_eq!(result.get_pixel(2, 0), [255, 0, 0, 0]);
+	}
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::transformation::DistanceTransformation;
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::processor::chamfer::{ChamferMask, ChamferProcessor};
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::result::DistanceTransformationResult;
+
+	fn pattern(width: u32, height: u32) -> InputField {
+		let data = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 7) * (x - 7) + (y - 5) * (y - 5) < 12 || (x > 13 && y > 8) || (x == 2 && y == 10)
+			})
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	fn single_cell_5_5() -> InputField {
+		let data = (0..25).map(|index| index == 12).collect();
+		InputField::from(BoolInputData::new(data, 5, 5))
+	}
+
+	fn brute_force_distance(field: &InputField, x: u32, y: u32, metric: fn(u32, u32) -> u32) -> u32 {
+		let own = field.data[x as usize + y as usize * field.width as usize];
+		let mut nearest = u32::MAX;
+		for ny in 0..field.height {
+			for nx in 0..field.width {
+				if field.data[nx as usize + ny as usize * field.width as usize] != own {
+					nearest = nearest.min(metric(nx.abs_diff(x), ny.abs_diff(y)));
+				}
+			}
+		}
+		nearest
+	}
+
+	fn assert_exact_metric(mask: ChamferMask, distance_type: DistanceType, metric: fn(u32, u32) -> u32) {
+		let field = pattern(20, 14);
+		let chamfer = ChamferProcessor::new(mask).process_chamfer(&field);
+		let mut dt = DistanceTransformation::from(ChamferProcessor::new(mask).process(&field));
+		dt.distance_type(distance_type);
+		let values = ScalarField::from(&dt.result());
+
+		for y in 0..field.height {
+			for x in 0..field.width {
+				let expected = brute_force_distance(&field, x, y, metric);
+				assert_eq!(chamfer.distance(x, y), Some(expected), "distance of cell ({}, {})", x, y);
+				assert_eq!(values.get(x, y), expected as f32, "distance of the nearest cell of ({}, {})", x, y);
+			}
+		}
+	}
+
+	#[test]
+	fn city_block_is_exact_rectilinear_distance() {
+		assert_exact_metric(ChamferMask::CityBlock, DistanceType::RectilinearDistance, |dx, dy| dx + dy);
+	}
+
+	#[test]
+	fn chessboard_is_exact_chebyshev_distance() {
+		assert_exact_metric(ChamferMask::Chessboard, DistanceType::ChebyshevDistance, |dx, dy| dx.max(dy));
+	}
+
+	#[test]
+	fn three_four_mask() {
+		let chamfer = ChamferProcessor::new(ChamferMask::ThreeFour).process_chamfer(&single_cell_5_5());
+
+		assert_eq!(chamfer.distance(0, 2), Some(6));
+		assert_eq!(chamfer.distance(0, 1), Some(7));
+		assert_eq!(chamfer.distance(0, 0), Some(8));
+		assert_eq!(chamfer.distance(2, 2), Some(3));
+		let nearest = chamfer.nearest_cell_position(0, 0).unwrap();
+		assert_eq!((nearest.x, nearest.y), (2, 2));
+		assert_eq!(ScalarField::from(&chamfer).get(0, 2), 2.0);
+		assert_eq!(ScalarField::from(&chamfer).get(2, 2), -1.0);
+	}
+
+	#[test]
+	fn five_seven_eleven_mask() {
+		let chamfer = ChamferProcessor::new(ChamferMask::FiveSevenEleven).process_chamfer(&single_cell_5_5());
+
+		assert_eq!(chamfer.distance(0, 2), Some(10));
+		assert_eq!(chamfer.distance(0, 1), Some(11));
+		assert_eq!(chamfer.distance(0, 0), Some(14));
+		assert_eq!(chamfer.distance(1, 1), Some(7));
+	}
+
+	#[test]
+	fn integer_distances_as_transformation_result() {
+		let chamfer = ChamferProcessor::new(ChamferMask::ThreeFour).process_chamfer(&single_cell_5_5());
+		let result = DistanceTransformationResult::from(&chamfer);
+		let values = ScalarField::from(&result);
+
+		assert_eq!((result.width, result.height, result.num_channels), (5, 5, 1));
+		assert_eq!(values.get(0, 0), 8.0);
+		assert_eq!(values.get(0, 1), 7.0);
+	}
+
+	#[test]
+	fn field_without_other_layer_has_no_distances() {
+		let field = InputField::from(BoolInputData::new(vec![false; 6], 3, 2));
+		let chamfer = ChamferProcessor::new(ChamferMask::Chessboard).process_chamfer(&field);
+
+		assert_eq!(chamfer.distance(1, 1), None);
+		assert!(chamfer.nearest_cell_position(1, 1).is_none());
+	}
+
+	#[test]
+	fn masks_for_distance_types() {
+		assert_eq!(ChamferMask::for_distance_type(DistanceType::ChebyshevDistance), Some(ChamferMask::Chessboard));
+		assert_eq!(ChamferMask::for_distance_type(DistanceType::RectilinearDistance), Some(ChamferMask::CityBlock));
+		assert_eq!(ChamferMask::for_distance_type(DistanceType::EuclideanDistance), None);
+	}
+
+	#[test]
+	#[should_panic]
+	fn spacing_must_be_one() {
+		let field = single_cell_5_5().spacing(Spacing::new(2.0, 2.0));
+		ChamferProcessor::new(ChamferMask::Chessboard).process(&field);
+	}
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::operation::contour::ContourExtractor;
+
+	// helper method to get a 4x4 field with a 2x2 square in the center
+	fn get_field_4_4_centered_square() -> ScalarField {
+		ScalarField::new(vec![
+			2.0, 1.0, 1.0, 2.0,
+			1.0, -1.0, -1.0, 1.0,
+			1.0, -1.0, -1.0, 1.0,
+			2.0, 1.0, 1.0, 2.0,
+		], 4, 4)
+	}
+
+	#[test]
+	fn extract_single_closed_contour() {
+		let field = get_field_4_4_centered_square();
+		let contours = ContourExtractor::new().extract(&field, 0.0);
+
+		assert_eq!(contours.len(), 1);
+		let points = &contours[0].points;
+		assert_eq!(points.len(), 8);
+
+		// all points are on the boundary half way between the cells
+		for (x, y) in points.iter() {
+			assert!(*x >= 0.5 && *x <= 2.5 && *y >= 0.5 && *y <= 2.5);
+			assert!(*x == 0.5 || *x == 2.5 || *y == 0.5 || *y == 2.5);
+		}
+	}
+
+	#[test]
+	fn contours_are_closed_at_the_border() {
+		let field = ScalarField::new(vec![-1.0, -1.0, 1.0, -1.0, -1.0, 1.0], 3, 2);
+		let contours = ContourExtractor::new().extract(&field, 0.0);
+
+		assert_eq!(contours.len(), 1);
+		assert!(contours[0].points.contains(&(-0.5, 0.0)));
+		assert!(contours[0].points.contains(&(1.5, 1.0)));
+	}
+
+	#[test]
+	fn extract_multiple_levels() {
+		let field = get_field_4_4_centered_square();
+		let contours = ContourExtractor::new().extract_levels(&field, &[0.0, 1.5, -10.0]);
+
+		assert_eq!(contours.len(), 2);
+		assert_eq!(contours[0].level, 0.0);
+		assert_eq!(contours[1].level, 1.5);
+	}
+
+	#[test]
+	fn simplify_contour() {
+		let field = ScalarField::new(vec![
+			1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+			1.0, -1.0, -1.0, -1.0, -1.0, 1.0,
+			1.0, -1.0, -1.0, -1.0, -1.0, 1.0,
+			1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+		], 6, 4);
+
+		let contours = ContourExtractor::new().extract(&field, 0.0);
+		assert_eq!(contours[0].points.len(), 12);
+
+		// the points on the straight lines are removed
+		let contours = ContourExtractor::new().simplify(0.1).extract(&field, 0.0);
+		assert_eq!(contours[0].points.len(), 8);
+	}
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::Spacing;
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+
+	// helper method to get a 4x1 field with the left half inside
+	fn get_field_left() -> ScalarField {
+		ScalarField::new(vec![-2.0, -1.0, 1.0, 2.0], 4, 1)
+	}
+
+	// helper method to get a 4x1 field with the right half inside
+	fn get_field_right() -> ScalarField {
+		ScalarField::new(vec![2.0, 1.0, -1.0, -2.0], 4, 1)
+	}
+
+	#[test]
+	fn union_intersection_subtraction() {
+		let a = get_field_left();
+		let b = get_field_right();
+
+		assert_eq!(a.union(&b).data, vec![-2.0, -1.0, -1.0, -2.0]);
+		assert_eq!(a.intersection(&b).data, vec![2.0, 1.0, 1.0, 2.0]);
+		assert_eq!(a.subtraction(&b).data, vec![-2.0, -1.0, 1.0, 2.0]);
+		assert_eq!(b.subtraction(&b).data, vec![2.0, 1.0, 1.0, 2.0]);
+	}
+
+	#[test]
+	fn smooth_union() {
+		let a = get_field_left();
+		let b = get_field_right();
+
+		// without a radius the smooth union is equal to the union
+		assert_eq!(a.smooth_union(&b, 0.0).data, a.union(&b).data);
+
+		// within the radius the values get smaller than the minimum
+		let smooth = a.smooth_union(&b, 4.0);
+		assert!(smooth.data[1] < -1.0);
+		assert!(smooth.data[2] < -1.0);
+	}
+
+	#[test]
+	#[should_panic]
+	fn fields_must_have_the_same_dimensions() {
+		let a = get_field_left();
+		let b = ScalarField::new(vec![0.0; 4], 2, 2);
+		a.union(&b);
+	}
+
+	#[test]
+	fn reinitialize() {
+		// the union of two squares that overlap and have no valid distances anymore
+		let a = ScalarField::new(vec![
+			-5.0, -5.0, 5.0, 5.0, 5.0,
+			-5.0, -5.0, 5.0, 5.0, 5.0,
+		], 5, 2);
+		let b = ScalarField::new(vec![
+			5.0, -5.0, 5.0, 5.0, 5.0,
+			5.0, -5.0, 5.0, 5.0, 5.0,
+		], 5, 2);
+
+		let field = a.union(&b).reinitialize(&EightSideSweepProcessor {});
+		assert_eq!(field.data, vec![
+			-2.0, -1.0, 1.0, 2.0, 3.0,
+			-2.0, -1.0, 1.0, 2.0, 3.0,
+		]);
+	}
+
+	#[test]
+	fn reinitialize_keeps_the_spacing() {
+		let spacing = Spacing::new(0.5, 2.0);
+		let field = get_field_left().subtraction(&get_field_right()).spacing(spacing);
+		let reinitialized = field.reinitialize(&EightSideSweepProcessor {});
+
+		assert_eq!(reinitialized.spacing, spacing);
+		// the distances are measured in physical units: the cells are 0.5 wide
+		assert_eq!(reinitialized.data, vec![-1.0, -0.5, 0.5, 1.0]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn fields_must_have_the_same_spacing() {
+		get_field_left().union(&get_field_right().spacing(Spacing::new(0.5, 0.5)));
+	}
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod source_tests {
-    use rs_sdf::data::input::{InputField, BoolInputData, ByteInputData};
+    use rs_sdf::data::input::{InputField, BoolInputData, ByteInputData, is_supported_size};
+    use rs_sdf::processor::sweep::EightSideSweepProcessor;
+    use rs_sdf::processor::SourceProcessor;
     use bitvec::prelude::*;
 
     #[test]
@@ -38,4 +40,33 @@ mod source_tests {
         f.invert();
         assert_eq!(f.data, bitvec![1, 0, 1]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn fields_wider_than_16_bit_are_supported() {
+        let width = 70_000;
+        let b: Vec<bool> = (0..width).map(|x| x == 69_000).collect();
+        let f = InputField::from(BoolInputData::new(b, width, 1));
+        let df = EightSideSweepProcessor {}.process(&f);
+        let nearest = df.nearest_cell_position(0).unwrap();
+        assert_eq!((nearest.x, nearest.y), (69_000, 0));
+        assert_eq!(df.nearest_cell_position(69_999).unwrap().x, 69_000);
+    }
+
+    #[test]
+    fn nearest_cells_are_stored_as_32_bit_indices() {
+        let f = InputField::from(BoolInputData::new(vec![true, false, false, false, false, false, false, false, false, false], 5, 2));
+        let df = EightSideSweepProcessor {}.process(&f);
+        // two bytes for the layers and four bytes per cell for the index of the nearest cell
+        assert_eq!(df.storage_size(), 2 + 10 * 4);
+        assert_eq!(df.nearest_cell_position(9).unwrap().index, 0);
+    }
+
+    #[test]
+    fn fields_with_more_than_32_bit_cells_are_supported() {
+        assert!(is_supported_size(65_536, 65_536));
+        assert!(is_supported_size(70_000, 70_000));
+        assert!(is_supported_size(u32::MAX, 2));
+        // the layers of the cells can't be addressed anymore
+        assert!(!is_supported_size(u32::MAX, u32::MAX));
+    }
+}
@@ -4,12 +4,12 @@ mod tests {
 	use rs_sdf::distance::cartesian::CartesianDistance;
 	use rs_sdf::distance::chebyshev::ChebyshevDistance;
 	use rs_sdf::distance::euclid::{EuclideanDistance, EuclideanDistanceSquared};
-	use rs_sdf::distance::nearest_cell::{NearestCellPosition, NearestCellIndex};
+	use rs_sdf::distance::nearest_cell::{NearestCellPosition, NearestCellIndex, NearestCellIndexOffset};
 	use rs_sdf::distance::OneDimensionalDistanceCalculation;
 	use rs_sdf::distance::rectilinear::RectilinearDistance;
 	use rs_sdf::distance::TwoDimensionalDistanceCalculation;
 
-	fn setup_cell(index: u32, source_x: u16, source_y: u16, nearest_x: u16, nearest_y: u16, nearest_index: u32) -> Cell {
+	fn setup_cell(index: u64, source_x: u32, source_y: u32, nearest_x: u32, nearest_y: u32, nearest_index: u64) -> Cell {
 		Cell {
 			nearest_cell_position: Some(CellPosition {
 				x: nearest_x,
@@ -20,6 +20,7 @@ mod tests {
 			x: source_x,
 			y: source_y,
 			layer: CellLayer::Foreground,
+			border_offset: (0, 0),
 		}
 	}
 
@@ -151,6 +152,20 @@ mod tests {
 		assert_eq!(res, 99);
 	}
 
+	#[test]
+	fn nearest_cell_index_of_a_mask_with_more_than_32_bit_cells() {
+		// the last cells of a 70000x70000 mask
+		let c = setup_cell(69_999 + 69_999 * 70_000, 69_999, 69_999, 69_998, 69_999, 69_998 + 69_999 * 70_000);
+		assert_eq!(NearestCellIndex::calculate_u64(&c), 4_899_999_998);
+		assert_eq!(NearestCellIndexOffset::calculate_i64(&c), -1);
+
+		// the legacy 32 bit index is clamped
+		let res: u32 = NearestCellIndex::calculate_legacy(&c);
+		assert_eq!(res, u32::MAX);
+		let res: i32 = NearestCellIndexOffset::calculate_legacy(&c);
+		assert_eq!(res, -1);
+	}
+
 	#[test]
 	fn get_nearest_cell_position() {
 
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use rs_sdf::data::input::InputField;
+    use rs_sdf::data::input::{InputField, Spacing};
     use bitvec::prelude::*;
 
     #[test]
@@ -9,6 +9,7 @@ mod tests {
             width: 2,
             height: 2,
             data: bitvec![1, 0, 0, 1],
+            spacing: Spacing::default(),
         };
         assert_eq!(sf.width, 2);
         assert_eq!(sf.height, 2);
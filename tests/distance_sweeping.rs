@@ -55,13 +55,13 @@ mod tests {
 		let p = EightSideSweepProcessor {};
 		let df = p.process(&s);
 
-		assert!(df.data[0].nearest_cell_position.is_none());
+		assert!(df.cell(0).nearest_cell_position.is_none());
 
 		let s = get_source_1_1_filled();
 		let p = EightSideSweepProcessor {};
 		let df = p.process(&s);
 
-		assert!(df.data[0].nearest_cell_position.is_none());
+		assert!(df.cell(0).nearest_cell_position.is_none());
 	}
 
 	#[test]
@@ -74,22 +74,22 @@ mod tests {
 		// point in the field will be, so we just check for not equality
 		// with the cell itself and the other cell from the same layer.
 
-		assert!(df.data[0].nearest_cell_position.is_some());
-		assert!(df.data[1].nearest_cell_position.is_some());
-		assert!(df.data[2].nearest_cell_position.is_some());
-		assert!(df.data[3].nearest_cell_position.is_some());
+		assert!(df.cell(0).nearest_cell_position.is_some());
+		assert!(df.cell(1).nearest_cell_position.is_some());
+		assert!(df.cell(2).nearest_cell_position.is_some());
+		assert!(df.cell(3).nearest_cell_position.is_some());
 
-		assert_ne!(df.data[0].nearest_cell_position.unwrap().get_coordinates(), (1, 1));
-		assert_ne!(df.data[0].nearest_cell_position.unwrap().get_coordinates(), (0, 0));
+		assert_ne!(df.cell(0).nearest_cell_position.unwrap().get_coordinates(), (1, 1));
+		assert_ne!(df.cell(0).nearest_cell_position.unwrap().get_coordinates(), (0, 0));
 
-		assert_ne!(df.data[1].nearest_cell_position.unwrap().get_coordinates(), (1, 0));
-		assert_ne!(df.data[1].nearest_cell_position.unwrap().get_coordinates(), (0, 1));
+		assert_ne!(df.cell(1).nearest_cell_position.unwrap().get_coordinates(), (1, 0));
+		assert_ne!(df.cell(1).nearest_cell_position.unwrap().get_coordinates(), (0, 1));
 
-		assert_ne!(df.data[2].nearest_cell_position.unwrap().get_coordinates(), (1, 0));
-		assert_ne!(df.data[2].nearest_cell_position.unwrap().get_coordinates(), (0, 1));
+		assert_ne!(df.cell(2).nearest_cell_position.unwrap().get_coordinates(), (1, 0));
+		assert_ne!(df.cell(2).nearest_cell_position.unwrap().get_coordinates(), (0, 1));
 
-		assert_ne!(df.data[3].nearest_cell_position.unwrap().get_coordinates(), (1, 1));
-		assert_ne!(df.data[3].nearest_cell_position.unwrap().get_coordinates(), (0, 0));
+		assert_ne!(df.cell(3).nearest_cell_position.unwrap().get_coordinates(), (1, 1));
+		assert_ne!(df.cell(3).nearest_cell_position.unwrap().get_coordinates(), (0, 0));
 	}
 
 	#[test]
@@ -101,7 +101,7 @@ mod tests {
 		// totally empty or filled source fields will generate no meaningful output
 		// because we cannot detect where the nearest cells are
 		for n in 0..9 {
-			assert!(df.data[n].nearest_cell_position.is_none());
+			assert!(df.cell(n).nearest_cell_position.is_none());
 		}
 	}
 
@@ -114,7 +114,7 @@ mod tests {
 		// totally empty or filled source fields will generate no meaningful output
 		// because we cannot detect where the nearest cells are
 		for n in 0..9 {
-			assert!(df.data[n].nearest_cell_position.is_none());
+			assert!(df.cell(n).nearest_cell_position.is_none());
 		}
 	}
 
@@ -124,19 +124,73 @@ mod tests {
 		let processor = EightSideSweepProcessor {};
 		let df = processor.process(&s);
 
-		assert_eq!(df.data[0].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
-		assert_eq!(df.data[1].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
-		assert_eq!(df.data[2].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(0).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(1).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(2).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
 
-		assert_eq!(df.data[3].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(3).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
 		// because the center "dot" has many nearest cells we cannot
 		// predict the exact one that was set during sweeping,
 		// so we just test for is_some() here!
-		assert!(df.data[4].get_nearest_cell_position().is_some());
-		assert_eq!(df.data[5].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert!(df.cell(4).get_nearest_cell_position().is_some());
+		assert_eq!(df.cell(5).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
 
-		assert_eq!(df.data[6].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
-		assert_eq!(df.data[7].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
-		assert_eq!(df.data[8].get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(6).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(7).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
+		assert_eq!(df.cell(8).get_nearest_cell_position().unwrap().get_coordinates(), (1, 1));
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn correct_nearest_cells_for_single_row() {
+		// the left pass over the last row must not compare the first cell with the cell before it
+		// (the last cell of the previous row, which does not exist for a single row)
+		let s = InputField::from(BoolInputData::new(vec![false, true, false, false, true], 5, 1));
+		let df = EightSideSweepProcessor {}.process(&s);
+
+		let nearest: Vec<(u32, u32)> = df.cells()
+			.map(|cell| cell.get_nearest_cell_position().unwrap().get_coordinates())
+			.collect();
+		assert_eq!(nearest, vec![(1, 0), (0, 0), (1, 0), (4, 0), (3, 0)]);
+	}
+
+	#[test]
+	fn direct_neighbour_of_other_layer_is_kept() {
+		// the diagonal neighbour (1, 1) is visited after the direct neighbours (1, 0) and (0, 1),
+		// but it must not replace them as the nearest cell of the dot
+		let s = InputField::from(BoolInputData::new(vec![
+			true, false, false,
+			false, false, false,
+			false, false, false], 3, 3));
+		let df = EightSideSweepProcessor {}.process(&s);
+
+		assert_eq!(df.cell(0).distance_to_nearest_squared(), Some(1));
+	}
+
+	#[test]
+	fn cells_next_to_the_other_layer_have_a_distance_of_one() {
+		// before the sweep kept nearer cells, a diagonal neighbour of the other layer visited last
+		// replaced a direct one, so many cells on both sides of the boundary had a distance of √2
+		let (width, height) = (16u32, 12u32);
+		let data: Vec<bool> = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 7) * (x - 7) + (y - 5) * (y - 5) < 16 || (x > 12 && y > 8)
+			})
+			.collect();
+		let df = EightSideSweepProcessor {}.process(&InputField::from(BoolInputData::new(data.clone(), width, height)));
+
+		for y in 0..height {
+			for x in 0..width {
+				let layer = data[(x + y * width) as usize];
+				let has_direct_neighbour = [(1, 0), (-1, 0), (0, 1), (0, -1)].iter().any(|(dx, dy)| {
+					let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+					nx >= 0 && ny >= 0 && nx < width as i32 && ny < height as i32
+						&& data[(nx + ny * width as i32) as usize] != layer
+				});
+				if has_direct_neighbour {
+					assert_eq!(df.cell((x + y * width) as usize).distance_to_nearest_squared(), Some(1), "cell ({}, {})", x, y);
+				}
+			}
+		}
+	}
+}
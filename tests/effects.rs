@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::RgbaImage;
+	use rs_sdf::render::effects::EffectRenderer;
+
+	const RED: [u8; 4] = [255, 0, 0, 255];
+	const GREEN: [u8; 4] = [0, 255, 0, 255];
+	const BLUE: [u8; 4] = [0, 0, 255, 255];
+	const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+	// helper method to get the signed field of a 21x21 field with a disk (radius 5) in the center
+	fn get_disk_field() -> ScalarField {
+		let mut data = Vec::new();
+		for y in 0..21 {
+			for x in 0..21 {
+				data.push((x - 10) * (x - 10) + (y - 10) * (y - 10) <= 25);
+			}
+		}
+		let source = InputField::from(BoolInputData::new(data, 21, 21));
+		ScalarField::from(&EightSideSweepProcessor {}.process(&source))
+	}
+
+	#[test]
+	fn renders_outline_around_fill() {
+		let image = EffectRenderer::new()
+			.fill_color(RED)
+			.background_color(BLACK)
+			.outline(3.0, GREEN)
+			.render(&get_disk_field());
+
+		assert_eq!(image.get_pixel(10, 10), RED);
+		assert_eq!(image.get_pixel(10, 3), GREEN);
+		assert_eq!(image.get_pixel(10, 1), BLACK);
+	}
+
+	#[test]
+	fn renders_glows() {
+		let image = EffectRenderer::new()
+			.fill_color(RED)
+			.outer_glow(4.0, BLUE)
+			.inner_glow(3.0, GREEN)
+			.render(&get_disk_field());
+
+		// the inner glow fades out towards the center
+		assert_eq!(image.get_pixel(10, 10), RED);
+		let edge = image.get_pixel(10, 6);
+		assert!(edge[1] > 0 && edge[0] > 0);
+
+		// the outer glow fades out with the distance
+		let near = image.get_pixel(10, 4)[3];
+		let far = image.get_pixel(10, 2)[3];
+		assert!(near > far && far > 0);
+		assert_eq!(image.get_pixel(10, 4)[0..3], BLUE[0..3]);
+		assert_eq!(image.get_pixel(0, 0), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn renders_drop_shadow_below_fill() {
+		let image = EffectRenderer::new()
+			.scale(2.0)
+			.fill_color(RED)
+			.drop_shadow((4.0, 0.0), 0.0, BLACK)
+			.render(&get_disk_field());
+
+		assert_eq!(image.width, 42);
+		assert_eq!(image.get_pixel(21, 21), RED);
+		// the shadow is only visible on the right side of the disk
+		assert_eq!(image.get_pixel(36, 21), BLACK);
+		assert_eq!(image.get_pixel(6, 21), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn arranges_preview_sheet() {
+		let images = vec![
+			RgbaImage::filled(2, 2, RED),
+			RgbaImage::filled(2, 1, GREEN),
+			RgbaImage::filled(1, 1, BLUE),
+		];
+		let sheet = RgbaImage::sheet(&images, 2, BLACK).unwrap();
+
+		assert_eq!((sheet.width, sheet.height), (4, 4));
+		assert_eq!(sheet.get_pixel(1, 1), RED);
+		assert_eq!(sheet.get_pixel(3, 0), GREEN);
+		assert_eq!(sheet.get_pixel(3, 1), BLACK);
+		assert_eq!(sheet.get_pixel(0, 2), BLUE);
+		assert_eq!(sheet.get_pixel(3, 3), BLACK);
+	}
+
+	#[test]
+	fn rejects_sheet_exceeding_the_maximum_size() {
+		// the size is checked before the pixels are accessed
+		let wide = RgbaImage { width: u32::MAX, height: 1, data: Vec::new() };
+		assert!(RgbaImage::sheet(&[wide.clone(), wide], 2, BLACK).is_err());
+	}
+}
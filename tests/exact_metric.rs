@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::transformation::DistanceTransformation;
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::processor::boundary::{Boundary, BoundaryProcessor};
+	use rs_sdf::processor::chamfer::{ChamferMask, ChamferProcessor};
+	use rs_sdf::processor::narrow_band::NarrowBandProcessor;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::{Processor, SourceProcessor};
+
+	fn field_with_foreground(cells: &[(u32, u32)], width: u32, height: u32) -> InputField {
+		let data = (0..width * height)
+			.map(|index| cells.contains(&(index % width, index / width)))
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	fn distance_at_origin(processor: &dyn SourceProcessor, field: &InputField, distance_type: DistanceType) -> f32 {
+		let mut dt = DistanceTransformation::from(processor.process(field));
+		dt.distance_type(distance_type);
+		ScalarField::from(&dt.result()).get(0, 0)
+	}
+
+	#[test]
+	fn chebyshev_distance_of_euclidean_nearest_cell_is_too_large() {
+		// (4, 0) is the nearest cell under the euclidean distance, (3, 3) under the Chebyshev distance
+		let field = field_with_foreground(&[(4, 0), (3, 3)], 6, 6);
+		let distance_type = DistanceType::ChebyshevDistance;
+
+		assert_eq!(distance_at_origin(&EightSideSweepProcessor {}, &field, distance_type), 4.0);
+		assert_eq!(distance_at_origin(&*Processor::for_distance_type(distance_type, &field.spacing).processor, &field, distance_type), 3.0);
+	}
+
+	#[test]
+	fn rectilinear_distance_of_euclidean_nearest_cell_is_too_large() {
+		// (2, 2) is the nearest cell under the euclidean distance, (3, 0) under the rectilinear distance
+		let field = field_with_foreground(&[(3, 0), (2, 2)], 5, 5);
+		let distance_type = DistanceType::RectilinearDistance;
+
+		assert_eq!(distance_at_origin(&EightSideSweepProcessor {}, &field, distance_type), 4.0);
+		assert_eq!(distance_at_origin(&*Processor::for_distance_type(distance_type, &field.spacing).processor, &field, distance_type), 3.0);
+	}
+
+	#[test]
+	fn processors_report_minimized_distance_types() {
+		let unit = Spacing::default();
+		let sweep = EightSideSweepProcessor {};
+		// the sweep is not exact, not even for the euclidean distance
+		assert!(!sweep.minimizes(DistanceType::EuclideanDistance, &unit));
+		assert!(!sweep.minimizes(DistanceType::ChebyshevDistance, &unit));
+		assert!(!sweep.minimizes(DistanceType::RectilinearDistance, &unit));
+
+		let narrow_band = NarrowBandProcessor::new(16.0);
+		assert!(narrow_band.minimizes(DistanceType::EuclideanDistance, &Spacing::new(0.5, 2.0)));
+		assert!(!narrow_band.minimizes(DistanceType::ChebyshevDistance, &unit));
+
+		let chessboard = ChamferProcessor::new(ChamferMask::Chessboard);
+		assert!(chessboard.minimizes(DistanceType::ChebyshevDistance, &unit));
+		assert!(!chessboard.minimizes(DistanceType::RectilinearDistance, &unit));
+		assert!(!chessboard.minimizes(DistanceType::EuclideanDistance, &unit));
+		assert!(!ChamferProcessor::new(ChamferMask::ThreeFour).minimizes(DistanceType::EuclideanDistance, &unit));
+
+		let boundary = BoundaryProcessor::new(ChamferProcessor::new(ChamferMask::CityBlock)).boundary(Boundary::Periodic);
+		assert!(boundary.minimizes(DistanceType::RectilinearDistance, &unit));
+
+		for distance_type in [DistanceType::ChebyshevDistance, DistanceType::RectilinearDistance] {
+			assert!(Processor::for_distance_type(distance_type, &unit).processor.minimizes(distance_type, &unit));
+		}
+	}
+
+	#[test]
+	fn chamfer_masks_measure_distances_in_cells() {
+		let spacing = Spacing::new(1.0, 2.0);
+		let chessboard = ChamferProcessor::new(ChamferMask::Chessboard);
+		assert!(!chessboard.minimizes(DistanceType::ChebyshevDistance, &spacing));
+		assert!(!chessboard.minimizes(DistanceType::ChebyshevDistance, &Spacing::new(2.0, 2.0)));
+
+		// the sweep is used for other spacings
+		let processor = Processor::for_distance_type(DistanceType::ChebyshevDistance, &spacing).processor;
+		assert!(!processor.minimizes(DistanceType::ChebyshevDistance, &spacing));
+	}
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::transformation::TransformationResult;
+	use rs_sdf::processor::fast_marching::FastMarchingProcessor;
+	use rs_sdf::result::DistanceTransformationResult;
+
+	fn single_seed(width: u32, height: u32, x: u32, y: u32) -> InputField {
+		let data = (0..width * height).map(|index| index == x + y * width).collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	fn constant_speed(width: u32, height: u32, speed: f32) -> ScalarField {
+		ScalarField::new(vec![speed; (width * height) as usize], width, height)
+	}
+
+	#[test]
+	fn unit_speed_approximates_euclidean_distance() {
+		let seeds = single_seed(31, 31, 15, 15);
+		let times = FastMarchingProcessor {}.process(&seeds, &constant_speed(31, 31, 1.0));
+
+		assert_eq!(times.get(15, 15), 0.0);
+		// along the axes the front travels exactly one cell per time unit
+		assert_eq!(times.get(25, 15), 10.0);
+		assert_eq!(times.get(15, 0), 15.0);
+		// the first order scheme overestimates diagonal distances a little
+		for (x, y) in [(25, 25), (5, 22), (20, 3)] {
+			let (dx, dy) = (x as f32 - 15.0, y as f32 - 15.0);
+			let euclidean = (dx * dx + dy * dy).sqrt();
+			let time = times.get(x, y);
+			assert!(time >= euclidean && time < euclidean * 1.1, "time {} at ({}, {}), distance {}", time, x, y, euclidean);
+		}
+	}
+
+	#[test]
+	fn higher_speed_leads_to_earlier_arrival() {
+		let seeds = single_seed(21, 1, 0, 0);
+		let mut speed = constant_speed(21, 1, 1.0);
+		for x in 10..21 {
+			speed.data[x] = 4.0;
+		}
+		let times = FastMarchingProcessor {}.process(&seeds, &speed);
+
+		assert_eq!(times.get(10, 0), 9.25);
+		assert_eq!(times.get(20, 0), 11.75);
+	}
+
+	#[test]
+	fn cells_without_speed_are_not_reached() {
+		let seeds = single_seed(5, 3, 0, 1);
+		let mut speed = constant_speed(5, 3, 1.0);
+		// a wall at x = 2, which is open in the bottom row
+		speed.data[2] = 0.0;
+		speed.data[2 + 5] = 0.0;
+		let times = FastMarchingProcessor {}.process(&seeds, &speed);
+
+		assert_eq!(times.get(2, 0), f32::INFINITY);
+		assert!(times.get(4, 1) > 4.0 && times.get(4, 1).is_finite());
+
+		// a closed wall
+		speed.data[2 + 10] = 0.0;
+		let times = FastMarchingProcessor {}.process(&seeds, &speed);
+		assert_eq!(times.get(4, 1), f32::INFINITY);
+	}
+
+	#[test]
+	fn arrival_times_as_transformation_result() {
+		let seeds = single_seed(8, 4, 1, 1);
+		let times = FastMarchingProcessor {}.process(&seeds, &constant_speed(8, 4, 0.5));
+		let result = DistanceTransformationResult::from(&times);
+
+		assert_eq!((result.width, result.height, result.num_channels), (8, 4, 1));
+		assert_eq!(ScalarField::from(&result).data, times.data);
+		assert_eq!(times.get(5, 1), 8.0);
+	}
+
+	#[test]
+	fn arrival_times_as_clamped_u16_result() {
+		let seeds = single_seed(5, 3, 0, 1);
+		let mut speed = constant_speed(5, 3, 0.5);
+		speed.data[4] = 0.0;
+		speed.data[9] = 0.00001;
+		let times = FastMarchingProcessor {}.process(&seeds, &speed);
+
+		match TransformationResult::<u16>::from(&times) {
+			TransformationResult::OneDimensional(result) => {
+				assert_eq!((result.width, result.height), (5, 3));
+				assert_eq!(result.data[5], 0);
+				assert_eq!(result.data[6], 2);
+				// unreachable cells and times beyond the range are clamped
+				assert_eq!(result.data[4], u16::MAX);
+				assert_eq!(result.data[9], u16::MAX);
+			}
+			_ => panic!("arrival times have one dimension"),
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn speed_map_must_match_input() {
+		FastMarchingProcessor {}.process(&single_seed(4, 4, 0, 0), &constant_speed(4, 5, 1.0));
+	}
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::SQRT_2;
+
+	use bitvec::vec::BitVec;
+	use rs_sdf::data::geodesic::{OctileGeodesicField, ObstacleInputField};
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::processor::geodesic::OctileGeodesicProcessor;
+	use rs_sdf::result::DistanceTransformationResult;
+
+	/// A field with a single foreground cell at (0, 2) and a wall at x = 3, which is open at the bottom row.
+	fn floor_plan() -> OctileGeodesicField {
+		let (width, height) = (7, 5);
+		let data = (0..width * height).map(|index| index == 2 * width).collect();
+		let obstacles: BitVec = (0..width * height).map(|index| index % width == 3 && index / width < 4).collect();
+		let field = InputField::from(BoolInputData::new(data, width, height));
+		OctileGeodesicProcessor {}.process(&ObstacleInputField::new(field, obstacles))
+	}
+
+	fn assert_near(expected: f64, actual: f64) {
+		assert!((expected - actual).abs() < 1e-9, "expected {}, got {}", expected, actual);
+	}
+
+	#[test]
+	fn without_obstacles_distances_are_octile() {
+		let data = vec![true, false, false, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 3, 2));
+		let obstacles: BitVec = (0..6).map(|_| false).collect();
+		let geodesic = OctileGeodesicProcessor {}.process(&ObstacleInputField::new(field, obstacles));
+
+		assert_near(1.0, geodesic.distance(1, 0).unwrap());
+		assert_near(1.0 + SQRT_2, geodesic.distance(2, 1).unwrap());
+		assert_near(1.0, geodesic.distance(0, 0).unwrap());
+	}
+
+	#[test]
+	fn distances_travel_around_obstacles() {
+		let geodesic = floor_plan();
+
+		// around the end of the wall, the corner of the wall can not be cut
+		assert_near(4.0 * SQRT_2 + 2.0, geodesic.distance(6, 2).unwrap());
+		assert_near(2.0, geodesic.distance(2, 2).unwrap());
+		assert!(geodesic.distance(3, 0).is_none());
+		assert!(geodesic.is_obstacle(3, 0));
+	}
+
+	#[test]
+	fn predecessors_lead_to_the_nearest_cell() {
+		let geodesic = floor_plan();
+		let path = geodesic.path(6, 2);
+
+		assert_eq!((path[0].x, path[0].y), (6, 2));
+		assert_eq!(path.last().map(|cell| (cell.x, cell.y)), Some((0, 2)));
+		assert!(path.iter().all(|cell| !geodesic.is_obstacle(cell.x, cell.y)));
+		assert!(path.windows(2).all(|step| {
+			let (dx, dy) = (step[0].x as i64 - step[1].x as i64, step[0].y as i64 - step[1].y as i64);
+			dx.abs() <= 1 && dy.abs() <= 1
+		}));
+		let predecessor = geodesic.predecessor(6, 2).unwrap();
+		assert_eq!((predecessor.x, predecessor.y), (path[1].x, path[1].y));
+	}
+
+	#[test]
+	fn enclosed_cells_have_no_distance() {
+		let data = vec![true, false, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 5, 1));
+		let obstacles: BitVec = (0..5).map(|x| x == 2).collect();
+		let geodesic = OctileGeodesicProcessor {}.process(&ObstacleInputField::new(field, obstacles));
+
+		assert_near(1.0, geodesic.distance(1, 0).unwrap());
+		assert!(geodesic.distance(4, 0).is_none());
+		assert!(geodesic.predecessor(4, 0).is_none());
+		assert!(geodesic.path(4, 0).is_empty());
+	}
+
+	#[test]
+	fn geodesic_field_as_transformation_result() {
+		let geodesic = floor_plan();
+		let result = DistanceTransformationResult::from(&geodesic);
+		let values = ScalarField::from(&result);
+		let signed = ScalarField::from(&geodesic);
+
+		assert_eq!((result.width, result.height, result.num_channels), (7, 5, 1));
+		assert_eq!(values.get(6, 2), (4.0 * SQRT_2 + 2.0) as f32);
+		assert_eq!(signed.get(6, 2), (4.0 * SQRT_2 + 2.0) as f32);
+		assert_eq!(signed.get(0, 2), -1.0);
+	}
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::heatmap::{Colormap, HeatmapRenderer};
+
+	#[test]
+	fn maps_colormap_end_points() {
+		assert_eq!(Colormap::Viridis.color(0.0), [71, 1, 85, 255]);
+		assert_eq!(Colormap::Viridis.color(1.0), [252, 231, 33, 255]);
+		assert_eq!(Colormap::Inferno.color(-1.0), [0, 0, 0, 255]);
+		assert_eq!(Colormap::Inferno.color(2.0), [250, 255, 167, 255]);
+		assert_eq!(Colormap::BlueRed.color(0.5), [221, 221, 221, 255]);
+	}
+
+	#[test]
+	fn normalizes_to_field_range() {
+		let field = ScalarField::new(vec![10.0, 11.0, 12.0], 3, 1);
+		let image = HeatmapRenderer::new(Colormap::Viridis).render(&field);
+
+		assert_eq!(image.get_pixel(0, 0), Colormap::Viridis.color(0.0));
+		assert_eq!(image.get_pixel(1, 0), Colormap::Viridis.color(0.5));
+		assert_eq!(image.get_pixel(2, 0), Colormap::Viridis.color(1.0));
+
+		let image = HeatmapRenderer::new(Colormap::Viridis).range(0.0, 20.0).render(&field);
+		assert_eq!(image.get_pixel(0, 0), Colormap::Viridis.color(0.5));
+	}
+
+	#[test]
+	fn centers_diverging_colormap_on_zero() {
+		let field = ScalarField::new(vec![-2.0, 0.0, 4.0], 3, 1);
+		let image = HeatmapRenderer::new(Colormap::BlueRed).render(&field);
+
+		assert_eq!(image.get_pixel(0, 0), Colormap::BlueRed.color(0.25));
+		assert_eq!(image.get_pixel(1, 0), Colormap::BlueRed.color(0.5));
+		assert_eq!(image.get_pixel(2, 0), Colormap::BlueRed.color(1.0));
+	}
+
+	#[test]
+	fn draws_isolines() {
+		let source = InputField::from(BoolInputData::new(vec![
+			true, false, false, false, false, false, false, false,
+		], 8, 1));
+		let field = ScalarField::from(&EightSideSweepProcessor {}.process(&source));
+		let image = HeatmapRenderer::new(Colormap::Inferno)
+			.isolines(3.0)
+			.isoline_color([0, 255, 0, 255])
+			.render(&field);
+
+		// the distances are -1, 1, 2, ..., 7, so the lines are at 0, 3 and 6
+		let on_isoline: Vec<bool> = (0..8).map(|x| image.get_pixel(x, 0) == [0, 255, 0, 255]).collect();
+		assert_eq!(on_isoline, vec![true, false, true, false, false, true, false, false]);
+	}
+}
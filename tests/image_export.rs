@@ -35,11 +35,7 @@ mod tests {
 	#[test]
 	fn generates_png_file() {
 		// should generate a 1x1 pixel grey image
-		let d: DistanceField = DistanceField {
-			data: vec![Cell::new(CellLayer::Foreground, 0, 90, 90); 1], // Foreground(Distance::new(180, 180))
-			width: 1,
-			height: 1,
-		};
+		let d: DistanceField = DistanceField::from_cells(&[Cell::new(CellLayer::Foreground, 0, 90, 90)], 1, 1); // Foreground(Distance::new(180, 180))
 
 		create_temp_dir();
 
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::processor::jump_flooding::JumpFloodingProcessor;
+	use rs_sdf::processor::SourceProcessor;
+
+	fn pattern(width: u32, height: u32) -> InputField {
+		let data = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 20) * (x - 20) + (y - 14) * (y - 14) < 90 || (x > 40 && y > 25) || (x == 5 && y == 30) || (x + y) % 17 == 0 && x < 8
+			})
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	fn brute_force_distance(field: &InputField, x: u32, y: u32) -> f64 {
+		let own = field.data[x as usize + y as usize * field.width as usize];
+		let mut nearest = f64::INFINITY;
+		for ny in 0..field.height {
+			for nx in 0..field.width {
+				if field.data[nx as usize + ny as usize * field.width as usize] != own {
+					nearest = nearest.min(field.spacing.distance_squared(nx.abs_diff(x), ny.abs_diff(y)).sqrt());
+				}
+			}
+		}
+		nearest
+	}
+
+	/// Returns the number of cells with an exact distance and the largest error.
+	fn compare_with_brute_force(processor: &JumpFloodingProcessor, field: &InputField) -> (usize, f64) {
+		let df = processor.process(field);
+		let mut exact = 0;
+		let mut max_error: f64 = 0.0;
+		for cell in df.cells() {
+			let nearest = cell.nearest_cell_position.expect("every cell has a nearest cell");
+			assert_ne!(df.layer(nearest.index as usize), cell.layer, "the nearest cell is on the other layer");
+			let distance = field.spacing.distance_squared(cell.x.abs_diff(nearest.x), cell.y.abs_diff(nearest.y)).sqrt();
+			let error = distance - brute_force_distance(field, cell.x, cell.y);
+			assert!(error >= -1e-9);
+			if error < 1e-9 {
+				exact += 1;
+			}
+			max_error = max_error.max(error);
+		}
+		(exact, max_error)
+	}
+
+	#[test]
+	fn nearest_cells_are_nearly_exact() {
+		let field = pattern(50, 36);
+		let (exact, max_error) = compare_with_brute_force(&JumpFloodingProcessor::new(), &field);
+
+		assert!(exact * 100 >= field.data.len() * 99, "only {} of {} cells are exact", exact, field.data.len());
+		assert!(max_error < 1.0, "the largest error is {}", max_error);
+	}
+
+	#[test]
+	fn distances_use_spacing() {
+		let field = pattern(50, 36).spacing(Spacing::new(0.5, 1.5));
+		let (exact, max_error) = compare_with_brute_force(&JumpFloodingProcessor::new(), &field);
+
+		assert!(exact * 100 >= field.data.len() * 99, "only {} of {} cells are exact", exact, field.data.len());
+		assert!(max_error < 1.5, "the largest error is {}", max_error);
+	}
+
+	#[test]
+	fn steps_of_passes() {
+		assert_eq!(JumpFloodingProcessor::new().steps(50, 36), vec![1, 32, 16, 8, 4, 2, 1]);
+		assert_eq!(JumpFloodingProcessor::new().refinement(false).steps(16, 9), vec![8, 4, 2, 1]);
+		assert_eq!(JumpFloodingProcessor::new().refinement(false).steps(1, 1), Vec::<u32>::new());
+	}
+
+	#[test]
+	fn ties_keep_the_first_candidate() {
+		let data = vec![true, false, false, false, true];
+		let df = JumpFloodingProcessor::new().process(&InputField::from(BoolInputData::new(data, 5, 1)));
+
+		let nearest = df.get(2, 0).nearest_cell_position.unwrap();
+		assert_eq!((nearest.x, nearest.y), (0, 0));
+		let nearest = df.get(3, 0).nearest_cell_position.unwrap();
+		assert_eq!((nearest.x, nearest.y), (4, 0));
+	}
+
+	#[test]
+	fn field_without_other_layer_has_no_nearest_cells() {
+		let field = InputField::from(BoolInputData::new(vec![true; 12], 4, 3));
+		let df = JumpFloodingProcessor::new().process(&field);
+
+		assert!(df.cells().all(|cell| cell.nearest_cell_position.is_none()));
+	}
+
+	#[test]
+	fn does_not_claim_to_minimize_distances() {
+		let processor = JumpFloodingProcessor::new();
+		assert!(!processor.minimizes(DistanceType::EuclideanDistance, &Spacing::default()));
+		assert!(!processor.minimizes(DistanceType::ChebyshevDistance, &Spacing::default()));
+	}
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::label::LabelInputField;
+	use rs_sdf::processor::label::LabelSweepProcessor;
+
+	#[test]
+	fn splits_row_between_seeds() {
+		let field = LabelInputField::new(vec![
+			Some(1), None, None, None, None, None, Some(2), None, Some(3),
+		], 9, 1);
+		let result = LabelSweepProcessor {}.process(&field);
+
+		assert_eq!(result.labels(), vec![
+			Some(1), Some(1), Some(1), Some(1), Some(2), Some(2), Some(2), Some(2), Some(3),
+		]);
+		assert_eq!(result.distances().data, vec![0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0, 1.0, 0.0]);
+		assert!(result.get(0, 0).is_seed);
+		assert!(!result.get(1, 0).is_seed);
+	}
+
+	#[test]
+	fn matches_brute_force_nearest_seed() {
+		let (w, h) = (24u32, 18u32);
+		let seeds = [(2u32, 3u32, 7u32), (20, 4, 8), (11, 15, 9), (12, 8, 7)];
+		let mut data = vec![None; w as usize * h as usize];
+		for (x, y, label) in seeds.iter() {
+			data[*x as usize + *y as usize * w as usize] = Some(*label);
+		}
+		let result = LabelSweepProcessor {}.process(&LabelInputField::new(data, w, h));
+
+		for y in 0..h {
+			for x in 0..w {
+				let expected = seeds.iter()
+					.map(|(sx, sy, _)| (*sx as f64 - x as f64).powi(2) + (*sy as f64 - y as f64).powi(2))
+					.fold(f64::MAX, f64::min)
+					.sqrt();
+				let cell = result.get(x, y);
+				assert_eq!(cell.distance(), Some(expected));
+
+				// the label is the one of the (or one of the equally) nearest seeds
+				let nearest = cell.nearest_seed_position.unwrap();
+				let seed = seeds.iter().find(|(sx, sy, _)| *sx == nearest.x && *sy == nearest.y).unwrap();
+				assert_eq!(cell.label, Some(seed.2));
+			}
+		}
+	}
+
+	#[test]
+	fn field_without_seeds_has_no_labels() {
+		let result = LabelSweepProcessor {}.process(&LabelInputField::new(vec![None; 4], 2, 2));
+		assert_eq!(result.labels(), vec![None; 4]);
+		assert_eq!(result.distances().data, vec![f32::INFINITY; 4]);
+	}
+}
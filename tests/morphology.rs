@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::operation::morphology::Morphology;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use bitvec::prelude::*;
+
+	// helper method to get a 5x5 source field with just a centered dot (other cells are empty)
+	fn get_source_5_5_centered_dot() -> InputField {
+		let mut data = vec![false; 25];
+		data[12] = true;
+		InputField::from(BoolInputData::new(data, 5, 5))
+	}
+
+	#[test]
+	fn dilate_with_different_metrics() {
+		let source = get_source_5_5_centered_dot();
+
+		let euclidean = Morphology::new(EightSideSweepProcessor {}, DistanceType::EuclideanDistance);
+		assert_eq!(euclidean.dilate(&source, 1.5).data, bitvec![
+			0, 0, 0, 0, 0,
+			0, 1, 1, 1, 0,
+			0, 1, 1, 1, 0,
+			0, 1, 1, 1, 0,
+			0, 0, 0, 0, 0,
+		]);
+
+		let chebyshev = Morphology::new(EightSideSweepProcessor {}, DistanceType::ChebyshevDistance);
+		assert_eq!(chebyshev.dilate(&source, 2.0).data, bitvec![1; 25]);
+
+		let rectilinear = Morphology::new(EightSideSweepProcessor {}, DistanceType::RectilinearDistance);
+		assert_eq!(rectilinear.dilate(&source, 1.0).data, bitvec![
+			0, 0, 0, 0, 0,
+			0, 0, 1, 0, 0,
+			0, 1, 1, 1, 0,
+			0, 0, 1, 0, 0,
+			0, 0, 0, 0, 0,
+		]);
+	}
+
+	#[test]
+	fn exact_operations_minimize_the_metric() {
+		// (4, 0) is the nearest cell of (0, 0) under the euclidean distance, (3, 3) under the Chebyshev distance
+		let mut data = vec![false; 36];
+		data[4] = true;
+		data[3 + 3 * 6] = true;
+		let source = InputField::from(BoolInputData::new(data, 6, 6));
+
+		let sweep = Morphology::new(EightSideSweepProcessor {}, DistanceType::ChebyshevDistance);
+		assert!(!sweep.dilate(&source, 3.0).data[0]);
+		let exact = Morphology::exact(DistanceType::ChebyshevDistance);
+		assert!(exact.dilate(&source, 3.0).data[0]);
+
+		let euclidean = Morphology::exact(DistanceType::EuclideanDistance);
+		assert_eq!(euclidean.dilate(&get_source_5_5_centered_dot(), 1.5).data,
+				   Morphology::new(EightSideSweepProcessor {}, DistanceType::EuclideanDistance).dilate(&get_source_5_5_centered_dot(), 1.5).data);
+	}
+
+	#[test]
+	fn erode_and_offset() {
+		let source = get_source_5_5_centered_dot();
+		let m = Morphology::new(EightSideSweepProcessor {}, DistanceType::ChebyshevDistance);
+
+		let grown = m.offset(&source, 1.0);
+		assert_eq!(m.offset(&grown, -1.0).data, source.data);
+		assert_eq!(m.erode(&grown, 1.0).data, source.data);
+		assert_eq!(m.offset(&source, 0.0).data, source.data);
+	}
+
+	#[test]
+	fn open_removes_small_details() {
+		let source = get_source_5_5_centered_dot();
+		let m = Morphology::new(EightSideSweepProcessor {}, DistanceType::EuclideanDistance);
+
+		assert_eq!(m.open(&source, 1.0).data, bitvec![0; 25]);
+		assert_eq!(m.close(&source, 1.0).data, source.data);
+	}
+
+	#[test]
+	#[should_panic]
+	fn metric_must_be_supported() {
+		Morphology::new(EightSideSweepProcessor {}, DistanceType::NearestCellIndex);
+	}
+}
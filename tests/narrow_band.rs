@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::DistanceField;
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::sampling::FieldSampler;
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::distance::euclid::EuclideanDistance;
+	use rs_sdf::processor::narrow_band::NarrowBandProcessor;
+	use rs_sdf::processor::SourceProcessor;
+
+	fn pattern(width: u32, height: u32) -> InputField {
+		let data = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 12) * (x - 12) + (y - 9) * (y - 9) < 36 || (x > 26 && y > 14)
+			})
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	fn brute_force_distance(field: &InputField, x: u32, y: u32) -> f64 {
+		let own = field.data[x as usize + y as usize * field.width as usize];
+		let mut nearest = f64::INFINITY;
+		for ny in 0..field.height {
+			for nx in 0..field.width {
+				if field.data[nx as usize + ny as usize * field.width as usize] != own {
+					let (dx, dy) = (nx as f64 - x as f64, ny as f64 - y as f64);
+					nearest = nearest.min((dx * dx + dy * dy).sqrt());
+				}
+			}
+		}
+		nearest
+	}
+
+	#[test]
+	fn band_distances_are_exact() {
+		let source = pattern(32, 24);
+		let band = NarrowBandProcessor::new(4.0).process_band(&source);
+		for y in 0..source.height {
+			for x in 0..source.width {
+				let expected = brute_force_distance(&source, x, y);
+				match band.distance(x, y) {
+					Some(distance) => assert_eq!(distance, expected, "cell ({}, {})", x, y),
+					None => assert!(expected > 4.0, "cell ({}, {}) has distance {}", x, y, expected),
+				}
+			}
+		}
+		assert!(band.band_size() < source.data.len());
+	}
+
+	#[test]
+	fn far_cells_are_clamped() {
+		let source = pattern(32, 24);
+		let band = NarrowBandProcessor::new(2.5).process_band(&source);
+		let scalar = ScalarField::from(&band);
+
+		// the top right corner is far outside, the center of the circle far inside
+		assert_eq!(band.distance(31, 0), None);
+		assert_eq!(scalar.value_at(31, 0), 2.5);
+		assert_eq!(band.distance(12, 9), None);
+		assert_eq!(scalar.value_at(12, 9), -2.5);
+		assert_eq!(band.value_at(12, 9), -2.5);
+	}
+
+	#[test]
+	fn distance_field_has_no_nearest_cells_for_far_cells() {
+		let source = pattern(32, 24);
+		let df: DistanceField = NarrowBandProcessor::new(3.0).process(&source);
+		assert_eq!(df.len(), source.data.len());
+		for cell in df.cells() {
+			let expected = brute_force_distance(&source, cell.x, cell.y);
+			if expected <= 3.0 {
+				assert_eq!(EuclideanDistance::calculate(&cell), expected);
+			} else {
+				assert!(cell.nearest_cell_position.is_none());
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_distances_below_one_cell() {
+		NarrowBandProcessor::new(0.5);
+	}
+}
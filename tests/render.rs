@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+	use std::fs::{create_dir_all, remove_dir, remove_file};
+	use std::path::Path;
+
+	use rs_sdf::data::DistanceField;
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::transformation::DistanceTransformation;
+	use rs_sdf::distance::{DistanceLayer, DistanceType};
+	use rs_sdf::export::image::{PngOutput, RgbaImageWriter};
+	use rs_sdf::input::image::PngInput;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::RgbaImage;
+	use rs_sdf::render::sdf::SdfRenderer;
+
+	const TEMP_DIR: &str = r"__tmp__render__dir__/";
+
+	// helper method to get a 5x2 distance field with the two left columns in the foreground
+	fn get_distance_field_5_2_left_filled() -> DistanceField {
+		let source = InputField::from(BoolInputData::new(vec![
+			true, true, false, false, false,
+			true, true, false, false, false,
+		], 5, 2));
+		EightSideSweepProcessor {}.process(&source)
+	}
+
+	#[test]
+	fn renders_signed_field_at_scale() {
+		let field = ScalarField::from(&get_distance_field_5_2_left_filled());
+		let image = SdfRenderer::new()
+			.scale(2.0)
+			.threshold(0.25)
+			.inverted()
+			.fill_color([255, 0, 0, 255])
+			.background_color([0, 0, 255, 255])
+			.render(&field);
+
+		assert_eq!(image.width, 10);
+		assert_eq!(image.height, 4);
+		assert_eq!(image.get_pixel(0, 0), [255, 0, 0, 255]);
+		assert_eq!(image.get_pixel(9, 3), [0, 0, 255, 255]);
+
+		// the edge is at x = 4.25 in the output, so only the pixel at x = 4 is anti-aliased
+		assert_eq!(image.get_pixel(3, 0), [255, 0, 0, 255]);
+		let [r, _, b, _] = image.get_pixel(4, 0);
+		assert!(r > 0 && b > 128 && b < 255);
+		assert_eq!(image.get_pixel(5, 0), [0, 0, 255, 255]);
+	}
+
+	#[test]
+	fn renders_distance_transformation_result() {
+		let mut dt = DistanceTransformation::from(get_distance_field_5_2_left_filled());
+		dt.filter(DistanceLayer::Foreground);
+		dt.distance_type(DistanceType::EuclideanDistance);
+		let result = dt.result();
+
+		let field = ScalarField::from(&result);
+		assert_eq!(field.data, vec![2.0, 1.0, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0, 0.0, 0.0]);
+
+		// without smoothing the edge is hard
+		let image = SdfRenderer::new().smoothing(0.0).render_result(&result);
+		assert_eq!(image.width, 5);
+		let alphas: Vec<u8> = (0..5).map(|x| image.get_pixel(x, 1)[3]).collect();
+		assert_eq!(alphas, vec![255, 255, 0, 0, 0]);
+	}
+
+	#[test]
+	fn blends_with_coverage() {
+		let mut image = RgbaImage::filled(1, 1, [0, 0, 0, 255]);
+		image.blend_pixel(0, 0, [255, 255, 255, 255], 0.5);
+		assert_eq!(image.get_pixel(0, 0), [128, 128, 128, 255]);
+
+		let mut image = RgbaImage::new(1, 1);
+		image.blend_pixel(0, 0, [255, 0, 0, 255], 0.5);
+		assert_eq!(image.get_pixel(0, 0), [255, 0, 0, 128]);
+	}
+
+	#[test]
+	fn writes_and_reads_rendered_png() {
+		create_dir_all(Path::new(TEMP_DIR)).unwrap();
+		let path = format!("{}render.png", TEMP_DIR);
+
+		let mut image = RgbaImage::new(2, 1);
+		image.set_pixel(0, 0, [10, 20, 30, 40]);
+		image.set_pixel(1, 0, [200, 0, 0, 255]);
+		PngOutput::new(&path).write_image(&image);
+
+		// the scalar field contains the first (red) channel
+		let field = PngInput::new(&path).scalar_field().unwrap();
+		assert_eq!(field.data, vec![10.0, 200.0]);
+
+		remove_file(&path).unwrap();
+		remove_dir(Path::new(TEMP_DIR)).unwrap();
+	}
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::DistanceField;
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::data::sampling::{FieldSampler, Interpolation};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+
+	// helper method to get a 5x2 distance field with the two left columns in the foreground
+	fn get_distance_field_5_2_left_filled() -> DistanceField {
+		let source = InputField::from(BoolInputData::new(vec![
+			true, true, false, false, false,
+			true, true, false, false, false,
+		], 5, 2));
+		EightSideSweepProcessor {}.process(&source)
+	}
+
+	#[test]
+	fn sample_at_cell_centers() {
+		let df = get_distance_field_5_2_left_filled();
+
+		assert_eq!(df.sample(0.0, 0.0), -2.0);
+		assert_eq!(df.sample(1.0, 0.0), -1.0);
+		assert_eq!(df.sample(2.0, 0.0), 1.0);
+		assert_eq!(df.sample(4.0, 0.0), 3.0);
+	}
+
+	#[test]
+	fn sample_between_cells() {
+		let df = get_distance_field_5_2_left_filled();
+
+		// the boundary is halfway between the last foreground and the first background cell
+		assert_eq!(df.sample(1.5, 0.0), 0.0);
+		assert_eq!(df.sample(2.5, 0.0), 1.5);
+		assert_eq!(df.sample_interpolated(3.0, 0.0, Interpolation::Bicubic), 2.0);
+
+		// positions outside of the field are clamped
+		assert_eq!(df.sample(-3.0, 5.0), -2.0);
+	}
+
+	#[test]
+	fn gradient_and_normal() {
+		let df = get_distance_field_5_2_left_filled();
+
+		assert_eq!(df.gradient(3.0, 0.0), (1.0, 0.0));
+		assert_eq!(df.normal(2.5, 0.0), (1.0, 0.0));
+
+		let flat = ScalarField::new(vec![1.0; 4], 2, 2);
+		assert_eq!(flat.normal(0.5, 0.5), (0.0, 0.0));
+	}
+
+	#[test]
+	fn gradient_in_physical_units() {
+		// the distances grow by 0.5 per cell, which is one per physical unit
+		let source = InputField::from(BoolInputData::new(vec![true, true, false, false, false, true, true, false, false, false], 5, 2))
+			.spacing(Spacing::new(0.5, 3.0));
+		let df = EightSideSweepProcessor {}.process(&source);
+		assert_eq!(df.gradient(3.0, 0.0), (1.0, 0.0));
+
+		let field = ScalarField::new(vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0], 3, 2).spacing(Spacing::new(2.0, 1.0));
+		assert_eq!(field.gradient(1.0, 0.5), (0.5, 0.0));
+	}
+
+	#[test]
+	fn nearest_boundary_point() {
+		let df = get_distance_field_5_2_left_filled();
+
+		assert_eq!(df.nearest_boundary_point(4.0, 0.0), Some((1.5, 0.0)));
+		assert_eq!(df.nearest_boundary_point(0.0, 0.0), Some((1.5, 0.0)));
+
+		let empty = EightSideSweepProcessor {}.process(&InputField::from(BoolInputData::new(vec![false; 4], 2, 2)));
+		assert!(empty.nearest_boundary_point(0.0, 0.0).is_none());
+	}
+
+	#[test]
+	fn scalar_field_from_distance_field() {
+		let df = get_distance_field_5_2_left_filled();
+		let sf = ScalarField::from(&df);
+
+		assert_eq!(sf.data, vec![-2.0, -1.0, 1.0, 2.0, 3.0, -2.0, -1.0, 1.0, 2.0, 3.0]);
+	}
+}
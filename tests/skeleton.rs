@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::{DistanceField, CellLayer};
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::distance::DistanceLayer;
+	use rs_sdf::operation::skeleton::{SkeletonExtractor, SkeletonPoint};
+	use rs_sdf::processor::boundary::{Boundary, BoundaryProcessor};
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+
+	// helper method to get the distance field of a 13x5 bar with a small bump on its top
+	fn get_distance_field_bar_with_bump() -> DistanceField {
+		let rows = [
+			"...............",
+			"......#........",
+			".#############.",
+			".#############.",
+			".#############.",
+			".#############.",
+			".#############.",
+			"...............",
+		];
+		let data: Vec<bool> = rows.iter().flat_map(|row| row.chars().map(|c| c == '#')).collect();
+		let source = InputField::from(BoolInputData::new(data, 15, 8));
+		EightSideSweepProcessor {}.process(&source)
+	}
+
+	#[test]
+	fn skeleton_contains_center_line() {
+		let df = get_distance_field_bar_with_bump();
+		let skeleton = SkeletonExtractor::new().extract(&df);
+
+		for x in 3..12 {
+			assert!(skeleton.points.contains(&SkeletonPoint { x, y: 4, radius: 3.0 }));
+		}
+		// nothing next to the center line
+		assert!(skeleton.points.iter().all(|p| p.y != 3 || p.x < 4 || p.x > 10));
+	}
+
+	#[test]
+	fn prune_short_branches() {
+		let df = get_distance_field_bar_with_bump();
+
+		let skeleton = SkeletonExtractor::new().extract(&df);
+		assert!(skeleton.points.iter().any(|p| p.x == 6 && p.y == 1));
+
+		let pruned = SkeletonExtractor::new().prune(3).extract(&df);
+		assert!(pruned.points.iter().all(|p| p.y != 1 || p.x == 1 || p.x == 13));
+		assert!(pruned.points.len() < skeleton.points.len());
+	}
+
+	#[test]
+	fn skeleton_of_the_background() {
+		let df = get_distance_field_bar_with_bump();
+		let skeleton = SkeletonExtractor::new().layer(DistanceLayer::Background).extract(&df);
+
+		assert!(!skeleton.points.is_empty());
+		assert!(skeleton.points.iter().all(|p| df.get(p.x, p.y).layer == CellLayer::Background));
+	}
+
+	#[test]
+	fn skeleton_of_periodic_field() {
+		// a vertical stripe on the left, the background between the stripe and its periodic
+		// repetition on the right has its medial axis in the middle (between column 6 and 7)
+		let (width, height) = (12, 6);
+		let data: Vec<bool> = (0..width * height).map(|index| index % width < 2).collect();
+		let source = InputField::from(BoolInputData::new(data, width, height));
+		let df = BoundaryProcessor::new(EightSideSweepProcessor {})
+			.horizontal(Boundary::Periodic)
+			.vertical(Boundary::Periodic)
+			.process(&source);
+		let skeleton = SkeletonExtractor::new().layer(DistanceLayer::Background).extract(&df);
+
+		for y in 0..height {
+			assert!(skeleton.points.iter().any(|p| p.y == y && (p.x == 6 || p.x == 7) && p.radius == 5.0), "row {}", y);
+		}
+		assert!(skeleton.points.iter().all(|p| p.x == 6 || p.x == 7));
+	}
+}
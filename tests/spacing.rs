@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+	use std::fs::{create_dir_all, remove_dir, remove_file, File};
+
+	use bitvec::vec::BitVec;
+	use rs_sdf::data::geodesic::ObstacleInputField;
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::transformation::{DistanceTransformation, TransformationResult, TransformOutputGenerator};
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::export::image::{PngOutput, RgbaImageWriter, DistanceTransformationResultWriter};
+	use rs_sdf::input::DistanceInput;
+	use rs_sdf::input::image::PngInput;
+	use rs_sdf::processor::geodesic::OctileGeodesicProcessor;
+	use rs_sdf::processor::narrow_band::NarrowBandProcessor;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::RgbaImage;
+	use rs_sdf::result::{ChannelDataType, DistanceTransformationResult};
+
+	const TEMP_DIR: &str = r"__tmp__spacing__dir__/";
+	const RESULT_DIR: &str = r"__tmp__spacing__result__dir__/";
+
+	fn pattern(width: u32, height: u32, spacing: Spacing) -> InputField {
+		let data = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 8) * (x - 8) + (y - 6) * (y - 6) < 10 || (x > 14 && y > 9)
+			})
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height)).spacing(spacing)
+	}
+
+	fn brute_force_distance(field: &InputField, x: u32, y: u32) -> f64 {
+		let own = field.data[x as usize + y as usize * field.width as usize];
+		let mut nearest = f64::INFINITY;
+		for ny in 0..field.height {
+			for nx in 0..field.width {
+				if field.data[nx as usize + ny as usize * field.width as usize] != own {
+					nearest = nearest.min(field.spacing.distance_squared(nx.abs_diff(x), ny.abs_diff(y)).sqrt());
+				}
+			}
+		}
+		nearest
+	}
+
+	#[test]
+	fn distances_are_measured_in_physical_units() {
+		let field = pattern(20, 14, Spacing::new(0.5, 1.2));
+		let values = ScalarField::from(&EightSideSweepProcessor {}.process(&field));
+
+		for y in 0..field.height {
+			for x in 0..field.width {
+				let expected = brute_force_distance(&field, x, y) as f32;
+				assert!((values.get(x, y).abs() - expected).abs() < 1e-4,
+						"distance at ({}, {}) is {}, expected {}", x, y, values.get(x, y), expected);
+			}
+		}
+	}
+
+	#[test]
+	fn narrow_band_uses_physical_maximum_distance() {
+		let field = pattern(20, 14, Spacing::new(0.5, 1.2));
+		let band = NarrowBandProcessor::new(2.5).process_band(&field);
+
+		for y in 0..field.height {
+			for x in 0..field.width {
+				let expected = brute_force_distance(&field, x, y);
+				match band.distance(x, y) {
+					Some(distance) => assert!((distance - expected).abs() < 1e-9),
+					None => assert!(expected > 2.5),
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn transformation_results_are_physical() {
+		let data = vec![true, false, false, false, false, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 4, 2)).spacing(Spacing::new(0.5, 2.0));
+		let df = EightSideSweepProcessor {}.process(&field);
+
+		let mut dt = DistanceTransformation::from(df.clone());
+		dt.distance_type(DistanceType::ChebyshevDistance);
+		let chebyshev = ScalarField::from(&dt.result());
+		assert_eq!(chebyshev.get(3, 0), 1.5);
+		assert_eq!(chebyshev.get(1, 1), 2.0);
+
+		let mut dt = DistanceTransformation::from(df);
+		dt.distance_type(DistanceType::RectilinearDistance);
+		let rectilinear = ScalarField::from(&dt.result());
+		assert_eq!(rectilinear.get(3, 1), 3.5);
+	}
+
+	#[test]
+	fn result_types_of_unit_spacing_are_unchanged() {
+		let data = vec![true, false, false, false, false, false, false, false];
+		let expected = [
+			(DistanceType::EuclideanDistance, ChannelDataType::Float, 1, 8),
+			(DistanceType::EuclideanDistanceSquared, ChannelDataType::UnsignedInt, 1, 8),
+			(DistanceType::CartesianDistance, ChannelDataType::SignedInt, 2, 4),
+			(DistanceType::ChebyshevDistance, ChannelDataType::UnsignedInt, 1, 2),
+			(DistanceType::RectilinearDistance, ChannelDataType::UnsignedInt, 1, 4),
+			(DistanceType::NearestCellIndex, ChannelDataType::UnsignedInt, 1, 4),
+			(DistanceType::NearestCellIndexOffset, ChannelDataType::SignedInt, 1, 4),
+			(DistanceType::NearestCellPosition, ChannelDataType::UnsignedInt, 2, 2),
+		];
+		for (distance_type, data_type, num_channels, num_bytes) in expected {
+			let field = InputField::from(BoolInputData::new(data.clone(), 4, 2));
+			let mut dt = DistanceTransformation::from(EightSideSweepProcessor {}.process(&field));
+			dt.distance_type(distance_type);
+			let result = dt.result();
+			assert_eq!((result.data_type, result.num_channels, result.bit_depth.number_of_bytes()),
+					   (data_type, num_channels, num_bytes), "{:?}", distance_type);
+		}
+	}
+
+	#[test]
+	fn physical_distances_are_floats() {
+		let data = vec![true, false, false, false, false, false, false, false];
+		let expected = [
+			(DistanceType::EuclideanDistanceSquared, 1, 8),
+			(DistanceType::CartesianDistance, 2, 4),
+			(DistanceType::ChebyshevDistance, 1, 8),
+			(DistanceType::RectilinearDistance, 1, 8),
+		];
+		for (distance_type, num_channels, num_bytes) in expected {
+			let field = InputField::from(BoolInputData::new(data.clone(), 4, 2)).spacing(Spacing::new(0.5, 2.0));
+			let mut dt = DistanceTransformation::from(EightSideSweepProcessor {}.process(&field));
+			dt.distance_type(distance_type);
+			let result = dt.result();
+			assert_eq!((result.data_type, result.num_channels, result.bit_depth.number_of_bytes()),
+					   (ChannelDataType::Float, num_channels, num_bytes), "{:?}", distance_type);
+		}
+	}
+
+	#[test]
+	fn legacy_transformation_is_physical() {
+		let data = vec![true, false, false, false, false, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 4, 2)).spacing(Spacing::new(0.5, 2.0));
+		let mut dt = DistanceTransformation::from(EightSideSweepProcessor {}.process(&field));
+		dt.distance_type(DistanceType::RectilinearDistance);
+
+		match TransformOutputGenerator::<u16>::transform(&dt) {
+			TransformationResult::OneDimensional(result) => {
+				assert_eq!(result.spacing, Spacing::new(0.5, 2.0));
+				// 3.5 millimetres are truncated like the distances in cells
+				assert_eq!(result.data[7], 3);
+				assert_eq!(result.data[5], 2);
+			}
+			_ => panic!("the rectilinear distance has one dimension"),
+		}
+	}
+
+	#[test]
+	fn png_output_writes_the_spacing_of_the_result() {
+		create_dir_all(RESULT_DIR).unwrap();
+		let path = format!("{}cartesian.png", RESULT_DIR);
+
+		let data = vec![true, false, false, false, false, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 4, 2)).spacing(Spacing::new(0.5, 1.25));
+		let mut dt = DistanceTransformation::from(EightSideSweepProcessor {}.process(&field));
+		dt.distance_type(DistanceType::CartesianDistance);
+		PngOutput::new(&path).write_result(dt.result());
+
+		let decoder = png::Decoder::new(File::open(&path).unwrap());
+		let (_, reader) = decoder.read_info().unwrap();
+		let pixel_dimensions = reader.info().pixel_dims.unwrap();
+		remove_file(&path).unwrap();
+		remove_dir(RESULT_DIR).unwrap();
+
+		assert_eq!((pixel_dimensions.xppu, pixel_dimensions.yppu), (2000, 800));
+	}
+
+	#[test]
+	fn geodesic_steps_use_spacing() {
+		let data = vec![true, false, false, false];
+		let field = InputField::from(BoolInputData::new(data, 2, 2)).spacing(Spacing::new(3.0, 4.0));
+		let obstacles: BitVec = (0..4).map(|_| false).collect();
+		let geodesic = OctileGeodesicProcessor {}.process(&ObstacleInputField::new(field, obstacles));
+
+		assert_eq!(geodesic.distance(1, 0), Some(3.0));
+		assert_eq!(geodesic.distance(0, 1), Some(4.0));
+		assert_eq!(geodesic.distance(1, 1), Some(5.0));
+		assert_eq!(DistanceTransformationResult::from(&geodesic).spacing, Spacing::new(3.0, 4.0));
+	}
+
+	#[test]
+	fn png_files_keep_spacing() {
+		create_dir_all(TEMP_DIR).unwrap();
+		let path = format!("{}spacing.png", TEMP_DIR);
+
+		let mut image = RgbaImage::new(3, 2);
+		image.set_pixel(0, 0, [0, 0, 0, 255]);
+		PngOutput::new(&path).spacing(Spacing::new(0.5, 1.25)).write_image(&image);
+		let field = PngInput::new(&path).physical_spacing().source_field().unwrap();
+
+		remove_file(&path).unwrap();
+		remove_dir(TEMP_DIR).unwrap();
+
+		assert_eq!(field.spacing, Spacing::new(0.5, 1.25));
+		assert!(field.data[0]);
+	}
+
+	#[test]
+	fn png_spacing_is_only_read_on_request() {
+		// the file has a pHYs chunk with 72 dpi (2835 pixels per metre)
+		let path = "tests/test_assets/test_rgba_2x2_checkerboard.png";
+		assert!(PngInput::new(path).source_field().unwrap().spacing.is_unit());
+		let spacing = PngInput::new(path).physical_spacing().source_field().unwrap().spacing;
+		assert_eq!(spacing, Spacing::new(1000.0 / 2835.0, 1000.0 / 2835.0));
+	}
+
+	#[test]
+	#[should_panic]
+	fn spacing_must_be_positive() {
+		Spacing::new(0.0, 1.0);
+	}
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+	use std::fs::{create_dir_all, remove_dir, remove_file};
+	use std::path::Path;
+
+	use rs_sdf::export::image::{PngOutput, RgbaImageWriter};
+	use rs_sdf::input::font::FontInput;
+	use rs_sdf::input::image::PngInput;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::render::text::{GlyphAtlas, TextRenderer};
+
+	/// A subset of DejaVu Sans (the characters " !,AHIVdelorw") with the kerning in the GPOS table.
+	const FONT_FILE: &str = "tests/test_assets/fonts/DejaVuSansSubset.ttf";
+	/// The same subset with the kerning in the legacy kern table.
+	const KERN_TABLE_FONT_FILE: &str = "tests/test_assets/fonts/DejaVuSansSubsetKern.ttf";
+	const TEMP_DIR: &str = r"__tmp__text__dir__/";
+
+	fn get_atlas(characters: &str) -> GlyphAtlas {
+		let font = FontInput::new(FONT_FILE).unwrap();
+		GlyphAtlas::new(&font, characters, 32.0, 4, &EightSideSweepProcessor {})
+	}
+
+	#[test]
+	fn rejects_invalid_font() {
+		assert!(FontInput::new("tests/test_assets/invalid_file.dat").is_err());
+		assert!(FontInput::new("tests/test_assets/does_not_exist.ttf").is_err());
+	}
+
+	#[test]
+	fn rasterizes_glyphs() {
+		let font = FontInput::new(FONT_FILE).unwrap();
+
+		let space = font.glyph(' ', 32.0, 2);
+		assert!(space.field.is_none());
+		assert!(space.advance > 0.0);
+
+		// the stem of the capital I is inside, the padding is outside
+		let glyph = font.glyph('I', 32.0, 2);
+		let field = glyph.field.unwrap();
+		let center = field.width as usize / 2 + field.height as usize / 2 * field.width as usize;
+		assert!(field.data[center]);
+		assert!(!field.data[0]);
+		assert!(glyph.top < 0.0);
+	}
+
+	#[test]
+	fn uses_kerning_of_the_font() {
+		let atlas = get_atlas("AVo");
+		assert!(atlas.kerning('A', 'V') < 0.0);
+		assert_eq!(atlas.kerning('o', 'o'), 0.0);
+
+		// the kerned pair is narrower than the sum of both glyphs
+		let renderer = TextRenderer::new().size(32.0).margin(0.0);
+		let pair = renderer.render(&atlas, "AV");
+		let first = renderer.render(&atlas, "A");
+		let second = renderer.render(&atlas, "V");
+		assert!(pair.width < first.width + second.width);
+	}
+
+	#[test]
+	fn uses_pair_and_class_kerning_of_gpos_table() {
+		let font = FontInput::new(FONT_FILE).unwrap();
+		// DejaVu Sans has 2048 units per em, so the size is given in font units
+		assert_eq!(font.kerning('A', 'V', 2048.0), -131.0);
+		assert_eq!(font.kerning('V', 'o', 2048.0), -159.0);
+		assert_eq!(font.kerning('V', 'e', 2048.0), -159.0);
+		assert_eq!(font.kerning('V', 'A', 2048.0), 0.0);
+		assert_eq!(font.kerning('e', 'V', 2048.0), 0.0);
+	}
+
+	#[test]
+	fn uses_kern_table_without_gpos_kerning() {
+		let font = FontInput::new(KERN_TABLE_FONT_FILE).unwrap();
+		assert_eq!(font.kerning('A', 'V', 2048.0), -131.0);
+		assert_eq!(font.kerning('V', 'o', 2048.0), -159.0);
+		assert_eq!(font.kerning('o', 'V', 2048.0), 0.0);
+	}
+
+	#[test]
+	fn renders_rotated_line() {
+		let atlas = get_atlas("I");
+		let renderer = TextRenderer::new().size(64.0).fill_color([255, 0, 0, 255]);
+
+		let image = renderer.render(&atlas, "I");
+		let rotated = TextRenderer::new().size(64.0).rotation(90.0).render(&atlas, "I");
+		assert!((rotated.width as i32 - image.height as i32).abs() <= 1);
+		assert!((rotated.height as i32 - image.width as i32).abs() <= 1);
+
+		// the stem is in the horizontal center and the corners are empty
+		let opaque = (0..image.width).filter(|x| image.get_pixel(*x, image.height / 2)[3] == 255).count();
+		assert!(opaque > 4);
+		assert_eq!(image.get_pixel(image.width / 2, image.height / 2), [255, 0, 0, 255]);
+		assert_eq!(image.get_pixel(0, 0), [0, 0, 0, 0]);
+	}
+
+	#[test]
+	fn writes_text_png() {
+		create_dir_all(Path::new(TEMP_DIR)).unwrap();
+		let path = format!("{}text.png", TEMP_DIR);
+
+		let atlas = get_atlas("Helo, wrd!");
+		let image = TextRenderer::new().size(24.0).rotation(15.0).render(&atlas, "Hello, world!");
+		PngOutput::new(&path).write_image(&image);
+
+		let field = PngInput::new(&path).scalar_field().unwrap();
+		assert_eq!((field.width, field.height), (image.width, image.height));
+		assert!(field.data.contains(&255.0));
+
+		remove_file(&path).unwrap();
+		remove_dir(Path::new(TEMP_DIR)).unwrap();
+	}
+}
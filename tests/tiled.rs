@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+	use std::fs::{remove_file, write};
+	use std::path::PathBuf;
+
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::data::scalar::ScalarField;
+	use rs_sdf::data::tile::{RawInputFile, RawScalarFile, TileSource};
+	use rs_sdf::export::image::{PngOutput, RgbaImageWriter};
+	use rs_sdf::input::image::PngTileSource;
+	use rs_sdf::processor::narrow_band::NarrowBandProcessor;
+	use rs_sdf::processor::tiled::TiledProcessor;
+	use rs_sdf::render::RgbaImage;
+
+	/// A path in the temp directory that is unique for the test and the process,
+	/// so parallel runs do not share their files.
+	fn temp_path(name: &str) -> PathBuf {
+		std::env::temp_dir().join(format!("rs_sdf_tiled_{}_{}", std::process::id(), name))
+	}
+
+	fn pattern(width: u32, height: u32) -> Vec<bool> {
+		(0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				(x - 20) * (x - 20) + (y - 15) * (y - 15) < 100 || (x + y) % 23 == 0 || (x > 40 && y < 6)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn tiles_match_the_whole_field() {
+		let mut source = InputField::from(BoolInputData::new(pattern(47, 33), 47, 33));
+		let expected = ScalarField::from(&NarrowBandProcessor::new(5.5).process_band(&source));
+
+		for tile_size in [1, 7, 16, 64].iter() {
+			let mut result = ScalarField::new(vec![0.0; 47 * 33], 47, 33);
+			TiledProcessor::new(5.5).tile_size(*tile_size).process(&mut source, &mut result).unwrap();
+			assert_eq!(result.data, expected.data, "tile size {}", tile_size);
+		}
+	}
+
+	#[test]
+	fn streams_tiles_from_and_to_disk() {
+		let cells = pattern(47, 33);
+		let input_path = temp_path("input.raw");
+		let output_path = temp_path("output.raw");
+		write(&input_path, cells.iter().map(|cell| if *cell { 255u8 } else { 0 }).collect::<Vec<u8>>()).unwrap();
+
+		let mut source = RawInputFile::open(&input_path, 47, 33).unwrap();
+		let mut sink = RawScalarFile::create(&output_path, 47, 33).unwrap();
+		TiledProcessor::new(4.0).tile_size(10).process(&mut source, &mut sink).unwrap();
+
+		let mut stored = RawScalarFile::open(&output_path, 47, 33).unwrap();
+		let result = stored.read_region(0, 0, 47, 33).unwrap();
+		let expected = ScalarField::from(&NarrowBandProcessor::new(4.0).process_band(&InputField::from(BoolInputData::new(cells, 47, 33))));
+		assert_eq!(result.data, expected.data);
+
+		// regions are read directly from the file
+		let region = source.read_region(20, 15, 2, 1).unwrap();
+		assert_eq!(region.data.iter().copied().collect::<Vec<bool>>(), vec![true, true]);
+		assert_eq!(stored.read_region(46, 32, 1, 1).unwrap().data, vec![expected.get(46, 32)]);
+
+		remove_file(&input_path).unwrap();
+		remove_file(&output_path).unwrap();
+	}
+
+	#[test]
+	fn rejects_files_of_the_wrong_size() {
+		let path = temp_path("short.raw");
+		write(&path, vec![0u8; 10]).unwrap();
+		assert!(RawInputFile::open(&path, 4, 4).is_err());
+		remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn streams_tiles_from_png_rows() {
+		let cells = pattern(47, 33);
+		let path = temp_path("input.png");
+		let mut image = RgbaImage::new(47, 33);
+		for (index, cell) in cells.iter().enumerate() {
+			if *cell {
+				image.set_pixel(index as u32 % 47, index as u32 / 47, [0, 0, 0, 255]);
+			}
+		}
+		PngOutput::new(path.to_str().unwrap()).write_image(&image);
+
+		let mut source = PngTileSource::open(path.to_str().unwrap()).unwrap();
+		let mut result = ScalarField::new(vec![0.0; 47 * 33], 47, 33);
+		TiledProcessor::new(4.0).tile_size(10).process(&mut source, &mut result).unwrap();
+		let expected = ScalarField::from(&NarrowBandProcessor::new(4.0).process_band(&InputField::from(BoolInputData::new(cells, 47, 33))));
+		assert_eq!(result.data, expected.data);
+
+		// a region above the decoded rows decodes the file again
+		let region = source.read_region(20, 15, 2, 1).unwrap();
+		assert_eq!(region.data.iter().copied().collect::<Vec<bool>>(), vec![true, true]);
+
+		remove_file(&path).unwrap();
+	}
+
+	#[test]
+	#[should_panic(expected = "exceeds the field")]
+	fn rejects_regions_beyond_the_field() {
+		let mut source = InputField::from(BoolInputData::new(pattern(4, 4), 4, 4));
+		let _ = source.read_region(2, 0, u32::MAX, 1);
+	}
+}
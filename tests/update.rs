@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::DistanceField;
+	use rs_sdf::data::input::{InputField, BoolInputData, Spacing};
+	use rs_sdf::data::update::UpdateError;
+	use rs_sdf::distance::DistanceType;
+	use rs_sdf::processor::boundary::{Boundary, BoundaryProcessor};
+	use rs_sdf::processor::narrow_band::NarrowBandProcessor;
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+
+	/// An exact processor without a maximum distance.
+	struct UnboundedProcessor;
+
+	impl SourceProcessor for UnboundedProcessor {
+		fn process(&self, field: &InputField) -> DistanceField {
+			exact().process(field)
+		}
+
+		fn minimizes(&self, distance_type: DistanceType, spacing: &Spacing) -> bool {
+			exact().minimizes(distance_type, spacing)
+		}
+	}
+
+	fn pattern(width: u32, height: u32, edited: bool) -> InputField {
+		let data = (0..width * height)
+			.map(|index| {
+				let (x, y) = ((index % width) as i32, (index / width) as i32);
+				let circle = (x - 12) * (x - 12) + (y - 9) * (y - 9) < 36;
+				let block = x > 26 && y > 14;
+				// the edit removes a part of the circle and adds a small rectangle
+				if edited && (10..20).contains(&x) && (4..12).contains(&y) {
+					(x >= 16 && y >= 8) || (circle && x < 12)
+				} else {
+					circle || block
+				}
+			})
+			.collect();
+		InputField::from(BoolInputData::new(data, width, height))
+	}
+
+	/// A narrow band reaching beyond the diagonal of the field calculates the exact nearest cells.
+	fn exact() -> NarrowBandProcessor {
+		NarrowBandProcessor::new(64.0)
+	}
+
+	fn full_recompute(field: &InputField) -> DistanceField {
+		exact().process(field)
+	}
+
+	fn assert_same_cells(expected: &DistanceField, actual: &DistanceField) {
+		for (expected, actual) in expected.cells().zip(actual.cells()) {
+			assert_eq!(expected.layer, actual.layer, "layer of cell ({}, {})", actual.x, actual.y);
+			assert_eq!(expected.nearest_cell_position.map(|p| (p.x, p.y)),
+					   actual.nearest_cell_position.map(|p| (p.x, p.y)),
+					   "nearest cell of cell ({}, {})", actual.x, actual.y);
+		}
+	}
+
+	#[test]
+	fn update_matches_full_recompute() {
+		let before = full_recompute(&pattern(40, 30, false));
+		let edited = pattern(40, 30, true);
+
+		let mut updated = before;
+		updated.update(&edited, &exact(), 10, 4, 10, 8).unwrap();
+		assert_same_cells(&full_recompute(&edited), &updated);
+	}
+
+	#[test]
+	fn update_without_changes_keeps_field() {
+		let field = pattern(40, 30, false);
+		let before = full_recompute(&field);
+
+		let mut updated = before.clone();
+		updated.update(&field, &exact(), 0, 0, 40, 30).unwrap();
+		assert_same_cells(&before, &updated);
+	}
+
+	#[test]
+	fn update_of_field_without_boundary() {
+		let empty = InputField::from(BoolInputData::new(vec![false; 12], 4, 3));
+		let before = full_recompute(&empty);
+		let mut data = vec![false; 12];
+		data[5] = true;
+		let edited = InputField::from(BoolInputData::new(data, 4, 3));
+
+		let mut updated = before;
+		updated.update(&edited, &exact(), 1, 1, 1, 1).unwrap();
+		assert_same_cells(&full_recompute(&edited), &updated);
+	}
+
+	#[test]
+	fn update_matches_full_recompute_of_narrow_band() {
+		let processor = NarrowBandProcessor::new(3.0);
+		let before = processor.process(&pattern(40, 30, false));
+		let edited = pattern(40, 30, true);
+
+		let mut updated = before;
+		updated.update(&edited, &processor, 10, 4, 10, 8).unwrap();
+		assert_same_cells(&processor.process(&edited), &updated);
+	}
+
+	#[test]
+	fn update_matches_full_recompute_with_spacing() {
+		let processor = exact();
+		let spacing = Spacing::new(0.5, 1.5);
+		let before = processor.process(&pattern(40, 30, false).spacing(spacing));
+		let edited = pattern(40, 30, true).spacing(spacing);
+
+		let mut updated = before;
+		updated.update(&edited, &processor, 10, 4, 10, 8).unwrap();
+		assert_same_cells(&processor.process(&edited), &updated);
+	}
+
+	#[test]
+	fn update_requires_an_exact_and_bounded_processor() {
+		let field = pattern(40, 30, false);
+		let edited = pattern(40, 30, true);
+
+		// the sweep is not exact
+		let processor = EightSideSweepProcessor {};
+		let mut df = processor.process(&field);
+		assert_eq!(df.update(&edited, &processor, 10, 4, 10, 8), Err(UpdateError::NotExact));
+		assert_same_cells(&processor.process(&field), &df);
+
+		let mut df = UnboundedProcessor.process(&field);
+		assert_eq!(df.update(&edited, &UnboundedProcessor, 10, 4, 10, 8), Err(UpdateError::NotBounded));
+	}
+
+	#[test]
+	fn update_rejects_changed_spacing_and_borders() {
+		let field = pattern(40, 30, false);
+		let mut df = exact().process(&field);
+		let scaled = pattern(40, 30, true).spacing(Spacing::new(0.5, 0.5));
+		assert_eq!(df.update(&scaled, &exact(), 10, 4, 10, 8), Err(UpdateError::SpacingChanged));
+
+		let processor = BoundaryProcessor::new(exact()).boundary(Boundary::Periodic);
+		let mut df = processor.process(&field);
+		assert_eq!(df.update(&pattern(40, 30, true), &processor, 10, 4, 10, 8), Err(UpdateError::HasBorder));
+	}
+
+	#[test]
+	#[should_panic]
+	fn update_region_must_be_inside_of_field() {
+		let field = pattern(40, 30, false);
+		let _ = full_recompute(&field).update(&field, &exact(), 35, 0, 10, 10);
+	}
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+	use rs_sdf::data::DistanceField;
+	use rs_sdf::data::input::{InputField, BoolInputData};
+	use rs_sdf::distance::{DistanceLayer, DistanceType};
+	use rs_sdf::processor::sweep::EightSideSweepProcessor;
+	use rs_sdf::processor::SourceProcessor;
+	use rs_sdf::render::vectors::{VectorFieldRenderer, VectorMode};
+
+	const ARROW: [u8; 4] = [255, 0, 255, 255];
+	const FOREGROUND: [u8; 4] = [255, 255, 255, 255];
+	const BACKGROUND: [u8; 4] = [0, 0, 0, 255];
+
+	// helper method to get a 5x1 distance field with the two left cells in the foreground
+	fn get_distance_field_5_1_left_filled() -> DistanceField {
+		let source = InputField::from(BoolInputData::new(vec![true, true, false, false, false], 5, 1));
+		EightSideSweepProcessor {}.process(&source)
+	}
+
+	#[test]
+	fn draws_arrows_over_mask() {
+		let image = VectorFieldRenderer::new(VectorMode::Arrows, DistanceType::CartesianDistance)
+			.scale(10)
+			.layer(DistanceLayer::Background)
+			.mask_colors(FOREGROUND, BACKGROUND)
+			.arrow_color(ARROW)
+			.render(&get_distance_field_5_1_left_filled())
+			.unwrap();
+
+		assert_eq!((image.width, image.height), (50, 10));
+		// the arrow of the last cell points to the center of the nearest foreground cell
+		assert_eq!(image.get_pixel(45, 5), ARROW);
+		assert_eq!(image.get_pixel(30, 5), ARROW);
+		assert_eq!(image.get_pixel(15, 5), ARROW);
+		assert_eq!(image.get_pixel(12, 5), FOREGROUND);
+		assert_eq!(image.get_pixel(45, 1), BACKGROUND);
+	}
+
+	#[test]
+	fn both_distance_types_have_the_same_vectors() {
+		let df = get_distance_field_5_1_left_filled();
+		let cartesian = VectorFieldRenderer::new(VectorMode::Arrows, DistanceType::CartesianDistance).render(&df).unwrap();
+		let position = VectorFieldRenderer::new(VectorMode::Arrows, DistanceType::NearestCellPosition).render(&df).unwrap();
+		assert_eq!(cartesian.data, position.data);
+	}
+
+	#[test]
+	fn encodes_direction_and_length_as_hsv() {
+		let image = VectorFieldRenderer::new(VectorMode::Hsv, DistanceType::NearestCellPosition)
+			.scale(1)
+			.render(&get_distance_field_5_1_left_filled())
+			.unwrap();
+
+		// foreground cells point to the right (red), background cells to the left (cyan)
+		assert_eq!(image.get_pixel(0, 0), [170, 0, 0, 255]);
+		assert_eq!(image.get_pixel(1, 0), [85, 0, 0, 255]);
+		assert_eq!(image.get_pixel(2, 0), [0, 85, 85, 255]);
+		assert_eq!(image.get_pixel(4, 0), [0, 255, 255, 255]);
+	}
+
+	#[test]
+	fn rejects_images_exceeding_the_maximum_size() {
+		for mode in [VectorMode::Arrows, VectorMode::Hsv] {
+			let result = VectorFieldRenderer::new(mode, DistanceType::CartesianDistance)
+				.scale(u32::MAX)
+				.render(&get_distance_field_5_1_left_filled());
+			assert!(result.is_err());
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_one_dimensional_distance_types() {
+		VectorFieldRenderer::new(VectorMode::Hsv, DistanceType::EuclideanDistance);
+	}
+}